@@ -1,83 +1,634 @@
+use std::collections::{BTreeMap, HashMap};
+
 use anyhow::{Context, Result};
-use reqwest::{Client, header::USER_AGENT};
-use serde::{Deserialize, Serialize};
+use reqwest::Client;
+use semver::{Version, VersionReq};
+use serde::de::DeserializeOwned;
 
-const CRATES_IO_API: &str = "https://crates.io/api/v1/crates";
-const CRATES_IO_USER_AGENT: &str = "mcp-cratesio-probe/0.1 (contact: you@example.com)";
+use crate::http::{HttpConfig, OutboundClient};
 
-#[derive(Debug, Deserialize)]
-struct CratesIoSearchResponse {
-    #[serde(default)]
-    crates: Vec<CratesIoCrate>,
-}
+use super::types::{
+    CrateDependencies, CrateDependency, CrateDetail, CrateDownloads, CrateInfo, CrateSort,
+    CrateVersion, CrateVersionDetail, CratesIoCrateResponse, CratesIoDependenciesResponse,
+    CratesIoDependentVersion, CratesIoDownloadsResponse, CratesIoReverseDependenciesResponse,
+    CratesIoSearchResponse, CratesIoVersion, CratesSearchPage, DailyDownloads, DownloadSummary,
+    ReverseDependenciesPage, ReverseDependency, VersionDownloadSeries,
+};
 
-#[derive(Debug, Deserialize)]
-struct CratesIoCrate {
-    name: String,
-    max_stable_version: String,
-    description: Option<String>,
-    downloads: u64,
-    repository: Option<String>,
-    documentation: Option<String>,
-    homepage: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct CrateInfo {
-    pub name: String,
-    pub version: String,
-    pub description: Option<String>,
-    pub downloads: u64,
-    pub repository: Option<String>,
-    pub documentation: Option<String>,
-    pub homepage: Option<String>,
-}
+const CRATES_IO_API: &str = "https://crates.io/api/v1/crates";
+/// crates.io 下载统计中未单独列出的版本合并后的序列名
+const OTHER_VERSIONS: &str = "other";
+const TREND_WINDOW_DAYS: i64 = 30;
+/// crates.io 要求 User-Agent 中包含可联系到调用方的信息
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "openperplexity/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/FactrueSolin/opensearch-rmcp)"
+);
 
 #[derive(Clone)]
 pub struct CratesIoClient {
-    http: Client,
+    http: OutboundClient,
+    /// crates API 根地址，测试中指向本地模拟服务
+    api: String,
+}
+
+impl Default for CratesIoClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CratesIoClient {
     pub fn new() -> Self {
-        Self {
-            http: Client::new(),
+        Self::with_user_agent(DEFAULT_USER_AGENT).expect("default user agent is valid")
+    }
+
+    /// 使用自定义 User-Agent 创建客户端
+    pub fn with_user_agent(user_agent: &str) -> Result<Self> {
+        Self::with_config(user_agent, HttpConfig::default())
+    }
+
+    /// 使用自定义 User-Agent 与超时、重试、熔断配置创建客户端
+    pub fn with_config(user_agent: &str, http: HttpConfig) -> Result<Self> {
+        let http =
+            OutboundClient::new("crates.io", http, Client::builder().user_agent(user_agent))?;
+        Ok(Self {
+            http,
+            api: CRATES_IO_API.to_string(),
+        })
+    }
+
+    pub fn http(&self) -> &OutboundClient {
+        &self.http
+    }
+
+    /// 搜索 crates.io，`page` 从 1 开始
+    pub async fn search(
+        &self,
+        query: &str,
+        sort: CrateSort,
+        page: u32,
+        per_page: u32,
+    ) -> Result<CratesSearchPage> {
+        let payload: CratesIoSearchResponse = self
+            .get_json(
+                self.api.clone(),
+                &[
+                    ("q", query.to_string()),
+                    ("sort", sort.as_api_value().to_string()),
+                    ("page", page.to_string()),
+                    ("per_page", per_page.to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(CratesSearchPage {
+            crates: payload.crates.into_iter().map(CrateInfo::from).collect(),
+            total: payload.meta.total,
+        })
+    }
+
+    /// 获取 crate 的版本列表，并返回满足 `version_req` 的最高未 yank 版本的 features 与依赖
+    ///
+    /// 未指定 `version_req` 时选择最高的未 yank 稳定版本（没有稳定版本时退回预发布版本）。
+    pub async fn crate_detail(&self, name: &str, version_req: Option<&str>) -> Result<CrateDetail> {
+        validate_crate_name(name)?;
+        let version_req = version_req
+            .map(|req| {
+                VersionReq::parse(req)
+                    .with_context(|| format!("invalid version requirement: {req}"))
+            })
+            .transpose()?;
+
+        let payload = self.crate_payload(name).await?;
+
+        let mut versions: Vec<(Option<Version>, CratesIoVersion)> = payload
+            .versions
+            .into_iter()
+            .map(|version| (Version::parse(&version.num).ok(), version))
+            .collect();
+        versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let selected =
+            select_version(&versions, version_req.as_ref()).with_context(
+                || match &version_req {
+                    Some(req) => format!("no published non-yanked version of {name} matches {req}"),
+                    None => format!("{name} has no published non-yanked version"),
+                },
+            )?;
+        let dependencies = self.dependencies(name, &selected.num).await?;
+
+        let selected = CrateVersionDetail {
+            num: selected.num.clone(),
+            yanked: selected.yanked,
+            rust_version: selected.rust_version.clone(),
+            license: selected.license.clone(),
+            created_at: selected.created_at.clone(),
+            features: selected.features.clone(),
+            dependencies,
+        };
+
+        Ok(CrateDetail {
+            info: CrateInfo::from(payload.krate),
+            versions: versions
+                .iter()
+                .map(|(_, version)| CrateVersion::from(version))
+                .collect(),
+            selected,
+        })
+    }
+
+    async fn crate_payload(&self, name: &str) -> Result<CratesIoCrateResponse> {
+        self.get_json(format!("{}/{}", self.api, name), &[]).await
+    }
+
+    async fn dependencies(&self, name: &str, version: &str) -> Result<CrateDependencies> {
+        let payload: CratesIoDependenciesResponse = self
+            .get_json(
+                format!("{}/{}/{}/dependencies", self.api, name, version),
+                &[],
+            )
+            .await?;
+
+        let mut dependencies = CrateDependencies::default();
+        for dependency in payload.dependencies {
+            let group = match dependency.kind.as_str() {
+                "dev" => &mut dependencies.dev,
+                "build" => &mut dependencies.build,
+                _ => &mut dependencies.normal,
+            };
+            group.push(CrateDependency {
+                name: dependency.crate_id,
+                req: dependency.req,
+                optional: dependency.optional,
+                default_features: dependency.default_features,
+                features: dependency.features,
+                target: dependency.target,
+            });
         }
+        Ok(dependencies)
+    }
+
+    /// 分页获取依赖该 crate 的其他 crate（按被依赖方下载量从高到低），`page` 从 1 开始
+    pub async fn reverse_dependencies(
+        &self,
+        name: &str,
+        page: u32,
+        per_page: u32,
+    ) -> Result<ReverseDependenciesPage> {
+        validate_crate_name(name)?;
+        let payload: CratesIoReverseDependenciesResponse = self
+            .get_json(
+                format!("{}/{}/reverse_dependencies", self.api, name),
+                &[
+                    ("page", page.to_string()),
+                    ("per_page", per_page.to_string()),
+                ],
+            )
+            .await?;
+
+        let versions: HashMap<u64, &CratesIoDependentVersion> = payload
+            .versions
+            .iter()
+            .map(|version| (version.id, version))
+            .collect();
+        let dependencies = payload
+            .dependencies
+            .into_iter()
+            .filter_map(|dependency| {
+                let version = versions.get(&dependency.version_id)?;
+                Some(ReverseDependency {
+                    name: version.krate.clone(),
+                    version: version.num.clone(),
+                    req: dependency.req,
+                    kind: dependency.kind,
+                    optional: dependency.optional,
+                    downloads: version.downloads,
+                })
+            })
+            .collect();
+
+        Ok(ReverseDependenciesPage {
+            dependencies,
+            total: payload.meta.total,
+        })
+    }
+
+    /// 获取近 90 天按版本拆分的每日下载量，并计算近 30 天趋势与最新版本占比
+    ///
+    /// crates.io 只单独统计下载量最高的几个版本，其余版本合并在 `other` 序列中。
+    pub async fn download_stats(&self, name: &str) -> Result<CrateDownloads> {
+        validate_crate_name(name)?;
+        let (krate, downloads) = tokio::try_join!(
+            self.crate_payload(name),
+            self.get_json::<CratesIoDownloadsResponse>(
+                format!("{}/{}/downloads", self.api, name),
+                &[],
+            )
+        )?;
+
+        let version_nums: HashMap<u64, &str> = krate
+            .versions
+            .iter()
+            .map(|version| (version.id, version.num.as_str()))
+            .collect();
+
+        let mut by_version: BTreeMap<String, Vec<DailyDownloads>> = BTreeMap::new();
+        for item in downloads.version_downloads {
+            let version = version_nums
+                .get(&item.version)
+                .map(|num| num.to_string())
+                .unwrap_or_else(|| format!("#{}", item.version));
+            by_version.entry(version).or_default().push(DailyDownloads {
+                date: item.date,
+                downloads: item.downloads,
+            });
+        }
+        if !downloads.meta.extra_downloads.is_empty() {
+            by_version.insert(
+                OTHER_VERSIONS.to_string(),
+                downloads
+                    .meta
+                    .extra_downloads
+                    .into_iter()
+                    .map(|item| DailyDownloads {
+                        date: item.date,
+                        downloads: item.downloads,
+                    })
+                    .collect(),
+            );
+        }
+
+        let latest_version = krate
+            .krate
+            .max_stable_version
+            .clone()
+            .unwrap_or_else(|| krate.krate.max_version.clone());
+        let mut series: Vec<VersionDownloadSeries> = by_version
+            .into_iter()
+            .map(|(version, mut daily)| {
+                daily.sort_by(|a, b| a.date.cmp(&b.date));
+                VersionDownloadSeries {
+                    total: daily.iter().map(|item| item.downloads).sum(),
+                    version,
+                    daily,
+                }
+            })
+            .collect();
+        series.sort_by_key(|item| std::cmp::Reverse(item.total));
+
+        let summary = summarize_downloads(&series, &latest_version);
+        Ok(CrateDownloads { series, summary })
     }
 
     pub async fn search_simplified_json_string(&self, query: &str, limit: usize) -> Result<String> {
-        let payload = self
-            .http
-            .get(CRATES_IO_API)
-            .header(USER_AGENT, CRATES_IO_USER_AGENT)
-            .query(&[
-                ("q", query),
-                ("per_page", &limit.to_string()),
-                ("sort", "downloads"),
-            ])
-            .send()
+        let per_page = u32::try_from(limit).unwrap_or(u32::MAX);
+        let page = self
+            .search(query, CrateSort::Downloads, 1, per_page)
+            .await?;
+
+        serde_json::to_string(&page.crates).context("serialize crates.io response failed")
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: String,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        self.http
+            .send(self.http.inner().get(url).query(query))
             .await
             .context("request crates.io failed")?
             .error_for_status()
             .context("crates.io returned error status")?
-            .json::<CratesIoSearchResponse>()
+            .json::<T>()
             .await
-            .context("decode crates.io response failed")?;
+            .context("decode crates.io response failed")
+    }
+}
 
-        let crates = payload
-            .crates
-            .into_iter()
-            .map(|item| CrateInfo {
-                name: item.name,
-                version: item.max_stable_version,
-                description: item.description,
-                downloads: item.downloads,
-                repository: item.repository,
-                documentation: item.documentation,
-                homepage: item.homepage,
+/// 以序列中最新的日期为基准，计算近 30 天与之前 30 天的下载量及最新版本占比
+fn summarize_downloads(series: &[VersionDownloadSeries], latest_version: &str) -> DownloadSummary {
+    let anchor = series
+        .iter()
+        .flat_map(|item| item.daily.iter())
+        .filter_map(|item| day_number(&item.date))
+        .max();
+
+    let mut summary = DownloadSummary {
+        last_30_days: 0,
+        previous_30_days: 0,
+        trend_percent: None,
+        latest_version: latest_version.to_string(),
+        latest_version_last_30_days: 0,
+        latest_version_share: None,
+    };
+    let Some(anchor) = anchor else {
+        return summary;
+    };
+
+    for item in series {
+        for daily in &item.daily {
+            let Some(day) = day_number(&daily.date) else {
+                continue;
+            };
+            let age = anchor - day;
+            if (0..TREND_WINDOW_DAYS).contains(&age) {
+                summary.last_30_days += daily.downloads;
+                if item.version == latest_version {
+                    summary.latest_version_last_30_days += daily.downloads;
+                }
+            } else if (TREND_WINDOW_DAYS..TREND_WINDOW_DAYS * 2).contains(&age) {
+                summary.previous_30_days += daily.downloads;
+            }
+        }
+    }
+
+    if summary.previous_30_days > 0 {
+        let change = summary.last_30_days as f64 - summary.previous_30_days as f64;
+        summary.trend_percent = Some(change / summary.previous_30_days as f64 * 100.0);
+    }
+    if summary.last_30_days > 0 {
+        summary.latest_version_share =
+            Some(summary.latest_version_last_30_days as f64 / summary.last_30_days as f64);
+    }
+    summary
+}
+
+/// 将 `YYYY-MM-DD` 转换为自 1970-01-01 起的天数
+fn day_number(date: &str) -> Option<i64> {
+    let mut parts = date.get(..10)?.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Howard Hinnant 的 days_from_civil 算法
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// 在按版本从高到低排序的列表中选出目标版本
+fn select_version<'a>(
+    versions: &'a [(Option<Version>, CratesIoVersion)],
+    version_req: Option<&VersionReq>,
+) -> Option<&'a CratesIoVersion> {
+    let mut candidates = versions
+        .iter()
+        .filter(|(_, version)| !version.yanked)
+        .filter_map(|(parsed, version)| parsed.as_ref().map(|parsed| (parsed, version)));
+
+    match version_req {
+        Some(req) => candidates
+            .find(|(parsed, _)| req.matches(parsed))
+            .map(|(_, version)| version),
+        None => {
+            let candidates: Vec<_> = candidates.collect();
+            candidates
+                .iter()
+                .find(|(parsed, _)| parsed.pre.is_empty())
+                .or_else(|| candidates.first())
+                .map(|(_, version)| *version)
+        }
+    }
+}
+
+/// crate 名只允许字母、数字、`-` 与 `_`，避免拼接进 URL 路径时产生歧义
+fn validate_crate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        anyhow::bail!("invalid crate name: {name}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, Router, extract::Query, routing::get};
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    /// 按 `crate_detail` 的方式解析并从高到低排序
+    fn versions(entries: &[(&str, bool)]) -> Vec<(Option<Version>, CratesIoVersion)> {
+        let mut versions: Vec<_> = entries
+            .iter()
+            .enumerate()
+            .map(|(id, (num, yanked))| {
+                let version = CratesIoVersion {
+                    id: id as u64,
+                    num: num.to_string(),
+                    yanked: *yanked,
+                    rust_version: None,
+                    license: None,
+                    created_at: "2024-01-01T00:00:00Z".to_string(),
+                    downloads: 0,
+                    features: BTreeMap::new(),
+                };
+                (Version::parse(num).ok(), version)
             })
-            .collect::<Vec<_>>();
+            .collect();
+        versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+        versions
+    }
+
+    fn selected(
+        versions: &[(Option<Version>, CratesIoVersion)],
+        req: Option<&str>,
+    ) -> Option<String> {
+        let req = req.map(|req| VersionReq::parse(req).unwrap());
+        select_version(versions, req.as_ref()).map(|version| version.num.clone())
+    }
+
+    #[test]
+    fn selects_highest_stable_version() {
+        let versions = versions(&[
+            ("1.2.0", false),
+            ("2.0.0-beta.1", false),
+            ("1.3.0", true),
+            ("1.1.0", false),
+        ]);
+        assert_eq!(selected(&versions, None).as_deref(), Some("1.2.0"));
+    }
+
+    #[test]
+    fn falls_back_to_pre_release_without_stable() {
+        let versions = versions(&[
+            ("0.1.0-alpha.1", false),
+            ("0.1.0-alpha.2", false),
+            ("0.1.0-alpha.3", true),
+        ]);
+        assert_eq!(selected(&versions, None).as_deref(), Some("0.1.0-alpha.2"));
+    }
+
+    #[test]
+    fn selects_highest_match_for_requirement() {
+        let versions = versions(&[
+            ("0.9.5", false),
+            ("1.0.0", false),
+            ("1.4.2", false),
+            ("1.5.0", true),
+            ("2.0.0", false),
+            ("not-a-version", false),
+        ]);
+        assert_eq!(selected(&versions, Some("^1")).as_deref(), Some("1.4.2"));
+        assert_eq!(selected(&versions, Some("<1")).as_deref(), Some("0.9.5"));
+        assert_eq!(selected(&versions, Some("=1.5.0")), None);
+        assert_eq!(selected(&versions, Some("^3")), None);
+    }
+
+    #[test]
+    fn requirement_can_select_pre_release() {
+        let versions = versions(&[("1.0.0", false), ("2.0.0-rc.1", false)]);
+        assert_eq!(
+            selected(&versions, Some(">=2.0.0-rc.1")).as_deref(),
+            Some("2.0.0-rc.1")
+        );
+        assert_eq!(selected(&versions, Some("*")).as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn yanked_only_crate_has_no_version() {
+        let versions = versions(&[("1.0.0", true), ("0.1.0", true)]);
+        assert_eq!(selected(&versions, None), None);
+    }
+
+    fn series(version: &str, daily: &[(&str, u64)]) -> VersionDownloadSeries {
+        VersionDownloadSeries {
+            version: version.to_string(),
+            total: daily.iter().map(|(_, downloads)| downloads).sum(),
+            daily: daily
+                .iter()
+                .map(|(date, downloads)| DailyDownloads {
+                    date: date.to_string(),
+                    downloads: *downloads,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn converts_dates_to_day_numbers() {
+        assert_eq!(day_number("1970-01-01"), Some(0));
+        assert_eq!(day_number("1969-12-31"), Some(-1));
+        assert_eq!(day_number("2000-02-29"), Some(11_016));
+        assert_eq!(day_number("2000-03-01"), Some(11_017));
+        assert_eq!(day_number("2024-12-31"), Some(20_088));
+        // crates.io 的时间戳只取日期部分
+        assert_eq!(day_number("2024-12-31T12:00:00Z"), Some(20_088));
+        assert_eq!(day_number("2024-13-01"), None);
+        assert_eq!(day_number("2024-01-00"), None);
+        assert_eq!(day_number("2024-01"), None);
+        assert_eq!(day_number("not-a-date"), None);
+    }
+
+    #[test]
+    fn splits_downloads_into_30_day_windows() {
+        // 基准日 2024-03-31：近 30 天为 03-02..=03-31，之前 30 天为 02-01..=03-01
+        let series = vec![
+            series(
+                "1.1.0",
+                &[("2024-03-31", 40), ("2024-03-02", 20), ("2024-03-01", 10)],
+            ),
+            series(
+                "1.0.0",
+                &[("2024-03-15", 10), ("2024-02-01", 30), ("2024-01-31", 1000)],
+            ),
+            series(OTHER_VERSIONS, &[("2024-03-20", 30), ("2024-02-15", 20)]),
+        ];
+
+        let summary = summarize_downloads(&series, "1.1.0");
+        assert_eq!(summary.last_30_days, 100);
+        assert_eq!(summary.previous_30_days, 60);
+        assert_eq!(summary.latest_version, "1.1.0");
+        assert_eq!(summary.latest_version_last_30_days, 60);
+        assert_eq!(summary.latest_version_share, Some(0.6));
+        let trend = summary.trend_percent.unwrap();
+        assert!((trend - 66.666).abs() < 0.01, "{trend}");
+    }
+
+    #[test]
+    fn other_series_never_counts_as_latest_version() {
+        let series = vec![
+            series(OTHER_VERSIONS, &[("2024-03-31", 90)]),
+            series("2.0.0", &[("2024-03-31", 10)]),
+        ];
+        let summary = summarize_downloads(&series, "2.0.0");
+        assert_eq!(summary.last_30_days, 100);
+        assert_eq!(summary.latest_version_share, Some(0.1));
+        assert_eq!(summary.trend_percent, None);
+    }
+
+    #[test]
+    fn empty_downloads_have_no_ratios() {
+        let summary = summarize_downloads(&[], "1.0.0");
+        assert_eq!(summary.last_30_days, 0);
+        assert_eq!(summary.previous_30_days, 0);
+        assert_eq!(summary.trend_percent, None);
+        assert_eq!(summary.latest_version_share, None);
+
+        let summary = summarize_downloads(&[series("0.9.0", &[("2024-03-31", 5)])], "1.0.0");
+        assert_eq!(summary.latest_version_last_30_days, 0);
+        assert_eq!(summary.latest_version_share, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn joins_reverse_dependencies_with_versions() {
+        async fn reverse_dependencies(Query(query): Query<HashMap<String, String>>) -> Json<Value> {
+            assert_eq!(query["page"], "2");
+            assert_eq!(query["per_page"], "3");
+            Json(json!({
+                "dependencies": [
+                    {"version_id": 11, "req": "^1", "kind": "normal", "optional": false},
+                    {"version_id": 99, "req": "^0.9", "kind": "normal", "optional": false},
+                    {"version_id": 12, "req": "^1.2", "kind": "dev", "optional": true}
+                ],
+                "versions": [
+                    {"id": 12, "crate": "beta", "num": "0.3.0", "downloads": 500},
+                    {"id": 11, "crate": "alpha", "num": "2.1.0", "downloads": 9000}
+                ],
+                "meta": {"total": 42}
+            }))
+        }
+
+        let app = Router::new().route(
+            "/api/v1/crates/serde/reverse_dependencies",
+            get(reverse_dependencies),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api = format!("http://{}/api/v1/crates", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        let client = CratesIoClient {
+            api,
+            ..CratesIoClient::new()
+        };
 
-        serde_json::to_string(&crates).context("serialize crates.io response failed")
+        let page = client.reverse_dependencies("serde", 2, 3).await.unwrap();
+        assert_eq!(page.total, 42);
+        // 找不到对应版本的依赖被丢弃，其余保持接口返回的顺序
+        let names: Vec<_> = page
+            .dependencies
+            .iter()
+            .map(|dependency| dependency.name.as_str())
+            .collect();
+        assert_eq!(names, ["alpha", "beta"]);
+        let beta = &page.dependencies[1];
+        assert_eq!(beta.version, "0.3.0");
+        assert_eq!(beta.req, "^1.2");
+        assert_eq!(beta.kind, "dev");
+        assert!(beta.optional);
+        assert_eq!(beta.downloads, 500);
+        assert_eq!(page.dependencies[0].downloads, 9000);
     }
 }
@@ -1 +1,4 @@
 pub mod client;
+pub mod types;
+
+pub use client::CratesIoClient;
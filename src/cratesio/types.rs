@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// crates.io 搜索排序方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrateSort {
+    /// 按总下载量
+    #[default]
+    Downloads,
+    /// 按近 90 天下载量
+    RecentDownloads,
+    /// 按与关键词的相关度
+    Relevance,
+    /// 按发布时间（最新创建的 crate 优先）
+    NewlyAdded,
+}
+
+impl CrateSort {
+    pub fn as_api_value(&self) -> &'static str {
+        match self {
+            CrateSort::Downloads => "downloads",
+            CrateSort::RecentDownloads => "recent-downloads",
+            CrateSort::Relevance => "relevance",
+            CrateSort::NewlyAdded => "new",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrateSort::Downloads => "downloads",
+            CrateSort::RecentDownloads => "recent-downloads",
+            CrateSort::Relevance => "relevance",
+            CrateSort::NewlyAdded => "newly-added",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateInfo {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    /// 近 90 天下载量
+    pub recent_downloads: Option<u64>,
+    pub repository: Option<String>,
+    pub documentation: Option<String>,
+    pub homepage: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 一页 crates.io 搜索结果
+#[derive(Debug, Clone)]
+pub struct CratesSearchPage {
+    pub crates: Vec<CrateInfo>,
+    /// 匹配的 crate 总数
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CratesSearchResponse {
+    pub success: bool,
+    pub query: String,
+    pub sort: String,
+    pub page: u32,
+    pub per_page: u32,
+    /// 匹配的 crate 总数，可结合 page/per_page 判断是否还有下一页
+    pub total: u64,
+    pub crates: Vec<CrateInfo>,
+    pub error: Option<String>,
+}
+
+/// crate 的单个已发布版本
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateVersion {
+    pub num: String,
+    pub yanked: bool,
+    /// 声明的最低支持 Rust 版本（MSRV）
+    pub rust_version: Option<String>,
+    pub license: Option<String>,
+    /// 发布时间
+    pub created_at: String,
+    pub downloads: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateDependency {
+    pub name: String,
+    /// 版本要求，如 `^1.0`
+    pub req: String,
+    pub optional: bool,
+    pub default_features: bool,
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// 平台限定，如 `cfg(unix)`
+    pub target: Option<String>,
+}
+
+/// 按依赖类型分组的依赖列表
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CrateDependencies {
+    pub normal: Vec<CrateDependency>,
+    pub dev: Vec<CrateDependency>,
+    pub build: Vec<CrateDependency>,
+}
+
+/// 选中版本的详细信息
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateVersionDetail {
+    pub num: String,
+    pub yanked: bool,
+    pub rust_version: Option<String>,
+    pub license: Option<String>,
+    pub created_at: String,
+    /// 声明的 cargo features 及其启用的子 feature/依赖
+    pub features: BTreeMap<String, Vec<String>>,
+    pub dependencies: CrateDependencies,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrateDetail {
+    pub info: CrateInfo,
+    /// 全部版本，按语义化版本从高到低排序
+    pub versions: Vec<CrateVersion>,
+    /// 满足版本要求的最高未 yank 版本
+    pub selected: CrateVersionDetail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateDetailResponse {
+    pub success: bool,
+    pub name: String,
+    pub version_req: Option<String>,
+    #[serde(rename = "crate")]
+    pub crate_info: Option<CrateInfo>,
+    pub selected_version: Option<CrateVersionDetail>,
+    /// 版本总数（versions 可能因 max_versions 被截断）
+    pub versions_total: usize,
+    pub versions: Vec<CrateVersion>,
+    pub error: Option<String>,
+}
+
+/// 依赖当前 crate 的下游 crate
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReverseDependency {
+    /// 下游 crate 名称
+    pub name: String,
+    /// 下游 crate 依赖当前 crate 的最新版本
+    pub version: String,
+    /// 下游 crate 对当前 crate 的版本要求
+    pub req: String,
+    /// 依赖类型：normal、dev 或 build
+    pub kind: String,
+    pub optional: bool,
+    /// 下游 crate 的总下载量
+    pub downloads: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReverseDependenciesPage {
+    pub dependencies: Vec<ReverseDependency>,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DailyDownloads {
+    /// 日期，格式 `YYYY-MM-DD`
+    pub date: String,
+    pub downloads: u64,
+}
+
+/// 单个版本的每日下载序列；`other` 表示 crates.io 未单独统计的其余版本之和
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VersionDownloadSeries {
+    pub version: String,
+    /// 序列内下载量合计
+    pub total: u64,
+    pub daily: Vec<DailyDownloads>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DownloadSummary {
+    pub last_30_days: u64,
+    /// 31~60 天前的下载量
+    pub previous_30_days: u64,
+    /// 近 30 天相对之前 30 天的变化百分比；之前无下载时为空
+    pub trend_percent: Option<f64>,
+    /// 最新稳定版本
+    pub latest_version: String,
+    pub latest_version_last_30_days: u64,
+    /// 近 30 天下载中落在最新版本上的比例（0~1）
+    pub latest_version_share: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateDownloads {
+    /// 按下载量从高到低排列的各版本序列
+    pub series: Vec<VersionDownloadSeries>,
+    pub summary: DownloadSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateAdoptionResponse {
+    pub success: bool,
+    pub name: String,
+    pub page: u32,
+    pub per_page: u32,
+    /// 下游 crate 总数
+    pub reverse_dependencies_total: u64,
+    pub reverse_dependencies: Vec<ReverseDependency>,
+    pub downloads: Option<CrateDownloads>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoCrateResponse {
+    #[serde(rename = "crate")]
+    pub krate: CratesIoCrate,
+    #[serde(default)]
+    pub versions: Vec<CratesIoVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoVersion {
+    pub id: u64,
+    pub num: String,
+    #[serde(default)]
+    pub yanked: bool,
+    pub rust_version: Option<String>,
+    pub license: Option<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub downloads: u64,
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+}
+
+impl From<&CratesIoVersion> for CrateVersion {
+    fn from(version: &CratesIoVersion) -> Self {
+        Self {
+            num: version.num.clone(),
+            yanked: version.yanked,
+            rust_version: version.rust_version.clone(),
+            license: version.license.clone(),
+            created_at: version.created_at.clone(),
+            downloads: version.downloads,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoDependenciesResponse {
+    #[serde(default)]
+    pub dependencies: Vec<CratesIoDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoDependency {
+    pub crate_id: String,
+    pub req: String,
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default = "default_true")]
+    pub default_features: bool,
+    #[serde(default)]
+    pub features: Vec<String>,
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoReverseDependenciesResponse {
+    #[serde(default)]
+    pub dependencies: Vec<CratesIoReverseDependency>,
+    #[serde(default)]
+    pub versions: Vec<CratesIoDependentVersion>,
+    #[serde(default)]
+    pub meta: CratesIoMeta,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoReverseDependency {
+    pub version_id: u64,
+    pub req: String,
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoDependentVersion {
+    pub id: u64,
+    #[serde(rename = "crate")]
+    pub krate: String,
+    pub num: String,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoDownloadsResponse {
+    #[serde(default)]
+    pub version_downloads: Vec<CratesIoVersionDownloads>,
+    #[serde(default)]
+    pub meta: CratesIoDownloadsMeta,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoVersionDownloads {
+    pub version: u64,
+    pub downloads: u64,
+    pub date: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CratesIoDownloadsMeta {
+    #[serde(default)]
+    pub extra_downloads: Vec<CratesIoExtraDownloads>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoExtraDownloads {
+    pub date: String,
+    pub downloads: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoSearchResponse {
+    #[serde(default)]
+    pub crates: Vec<CratesIoCrate>,
+    #[serde(default)]
+    pub meta: CratesIoMeta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CratesIoMeta {
+    #[serde(default)]
+    pub total: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesIoCrate {
+    pub name: String,
+    pub max_stable_version: Option<String>,
+    pub max_version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    pub recent_downloads: Option<u64>,
+    pub repository: Option<String>,
+    pub documentation: Option<String>,
+    pub homepage: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl From<CratesIoCrate> for CrateInfo {
+    fn from(item: CratesIoCrate) -> Self {
+        Self {
+            name: item.name,
+            // 仅有预发布版本的 crate 没有 max_stable_version
+            version: item.max_stable_version.unwrap_or(item.max_version),
+            description: item.description,
+            downloads: item.downloads,
+            recent_downloads: item.recent_downloads,
+            repository: item.repository,
+            documentation: item.documentation,
+            homepage: item.homepage,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::{
+    Client, Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect,
+};
+use tracing::debug;
+
+use super::{extract::extract_article, types::FetchedPage};
+use crate::http::{HttpConfig, OutboundClient};
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 20;
+pub const DEFAULT_MAX_BYTES: usize = 5 * 1024 * 1024;
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "Mozilla/5.0 (compatible; openperplexity/",
+    env!("CARGO_PKG_VERSION"),
+    "; +https://github.com/FactrueSolin/opensearch-rmcp)"
+);
+const MAX_REDIRECTS: usize = 10;
+
+/// 网页抓取配置
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub http: HttpConfig,
+    /// 响应体最大字节数，超出部分会被丢弃
+    pub max_bytes: usize,
+    pub user_agent: String,
+    /// 是否允许抓取 localhost、内网等私有地址
+    pub allow_private_hosts: bool,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            http: HttpConfig::with_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            max_bytes: DEFAULT_MAX_BYTES,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            allow_private_hosts: false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PageFetcher {
+    http: OutboundClient,
+    max_bytes: usize,
+    allow_private_hosts: bool,
+}
+
+impl PageFetcher {
+    pub fn from_config(config: FetchConfig) -> Result<Self> {
+        let allow_private_hosts = config.allow_private_hosts;
+        // 每一跳重定向都重新校验目标地址，避免经由公网地址跳转到内网
+        let redirect_policy = redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if !allow_private_hosts && is_private_host(attempt.url()) {
+                attempt.error("redirect to a private address is not allowed")
+            } else {
+                attempt.follow()
+            }
+        });
+        let mut builder = Client::builder()
+            .user_agent(config.user_agent)
+            .redirect(redirect_policy);
+        if !allow_private_hosts {
+            // 域名在连接前解析并丢弃私有地址，初始地址与每一跳重定向都经过该检查
+            builder = builder.dns_resolver(Arc::new(PublicResolver));
+        }
+        let http = OutboundClient::new("fetch", config.http, builder)?;
+
+        Ok(Self {
+            http,
+            max_bytes: config.max_bytes,
+            allow_private_hosts,
+        })
+    }
+
+    /// 下载网页并提取正文，HTML 转换为 Markdown，纯文本原样返回
+    pub async fn fetch(&self, url: &str) -> Result<FetchedPage> {
+        let url = Url::parse(url).with_context(|| format!("invalid url: {url}"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("unsupported url scheme: {}", url.scheme());
+        }
+        if !self.allow_private_hosts && is_private_host(&url) {
+            anyhow::bail!("fetching private addresses is not allowed: {url}");
+        }
+
+        let request = self.http.inner().get(url).header(
+            "Accept",
+            "text/html,application/xhtml+xml,text/plain;q=0.9,*/*;q=0.1",
+        );
+        let mut response = self
+            .http
+            .send(request)
+            .await
+            .context("request page failed")?
+            .error_for_status()
+            .context("page returned error status")?;
+
+        let final_url = response.url().clone();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("text/html")
+            .to_ascii_lowercase();
+        let is_html = content_type.contains("html");
+        if !is_html && !content_type.starts_with("text/") {
+            anyhow::bail!("unsupported content type: {content_type}");
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.context("read page body failed")? {
+            body.extend_from_slice(&chunk);
+            if body.len() >= self.max_bytes {
+                debug!(url = %final_url, max_bytes = self.max_bytes, "Page body truncated");
+                body.truncate(self.max_bytes);
+                break;
+            }
+        }
+        let text = decode_body(&body, &content_type, is_html);
+
+        if !is_html {
+            return Ok(FetchedPage {
+                canonical_url: final_url.to_string(),
+                final_url: final_url.to_string(),
+                title: None,
+                byline: None,
+                published_at: None,
+                markdown: text.trim().to_string(),
+            });
+        }
+
+        let article = extract_article(&text, &final_url);
+        Ok(FetchedPage {
+            canonical_url: article
+                .canonical_url
+                .unwrap_or_else(|| final_url.to_string()),
+            final_url: final_url.to_string(),
+            title: article.title,
+            byline: article.byline,
+            published_at: article.published_at,
+            markdown: article.markdown,
+        })
+    }
+}
+
+/// 按 Content-Type 或 HTML `<meta charset>` 声明的编码解码，默认 UTF-8
+fn decode_body(body: &[u8], content_type: &str, is_html: bool) -> String {
+    let declared = charset_label(content_type).or_else(|| {
+        if !is_html {
+            return None;
+        }
+        let head = String::from_utf8_lossy(&body[..body.len().min(2048)]).to_ascii_lowercase();
+        charset_label(&head)
+    });
+    let encoding = declared
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+    let (text, _, _) = encoding.decode(body);
+    text.into_owned()
+}
+
+fn charset_label(text: &str) -> Option<String> {
+    let start = text.find("charset=")? + "charset=".len();
+    let label: String = text[start..]
+        .trim_start_matches(['"', '\''])
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+        .collect();
+    (!label.is_empty()).then_some(label)
+}
+
+/// 只返回公网地址的 DNS 解析器
+///
+/// 字面量 IP 不经过 DNS 解析，由 [`is_private_host`] 检查；域名（如 `host.docker.internal`、
+/// 单标签的内网主机名）解析到私有地址时在这里被拒绝。
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            let public: Vec<SocketAddr> = addrs
+                .iter()
+                .copied()
+                .filter(|addr| !is_private_ip(addr.ip()))
+                .collect();
+            if public.is_empty() {
+                debug!(host = %host, addrs = ?addrs, "Rejected host resolving to private addresses");
+                return Err(format!(
+                    "{host} resolves to a private address, fetching is not allowed"
+                )
+                .into());
+            }
+            Ok(Box::new(public.into_iter()) as Addrs)
+        })
+    }
+}
+
+fn is_private_host(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return true;
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return is_private_ip(ip);
+    }
+    let host = host.to_ascii_lowercase();
+    host == "localhost" || host.ends_with(".localhost") || host.ends_with(".local")
+}
+
+fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_ipv4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_private_ipv4(mapped);
+            }
+            // 64:ff9b::/96 NAT64 地址内嵌 IPv4
+            let segments = ip.segments();
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., a, b, c, d] = ip.octets();
+                return is_private_ipv4(Ipv4Addr::new(a, b, c, d));
+            }
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || is_unique_local(ip)
+                || is_link_local(ip)
+        }
+    }
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        // 0.0.0.0/8 本网络
+        || ip.octets()[0] == 0
+        // 100.64.0.0/10 运营商级 NAT
+        || (ip.octets()[0] == 100 && (ip.octets()[1] & 0b1100_0000) == 64)
+        // 198.18.0.0/15 基准测试网络
+        || (ip.octets()[0] == 198 && (ip.octets()[1] & 0b1111_1110) == 18)
+        // 240.0.0.0/4 保留地址
+        || ip.octets()[0] >= 240
+}
+
+fn is_unique_local(ip: Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xfe00) == 0xfc00
+}
+
+fn is_link_local(ip: Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use axum::{Router, http::StatusCode, response::IntoResponse, routing::get};
+
+    use super::*;
+
+    #[test]
+    fn rejects_private_literal_hosts() {
+        for url in [
+            "http://127.0.0.1/",
+            "http://10.1.2.3/",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/",
+            "http://[::1]/",
+            "http://[fd00::1]/",
+            "http://[::ffff:192.168.1.1]/",
+            "http://[64:ff9b::a00:1]/",
+            "http://localhost:8080/",
+            "http://printer.local/",
+        ] {
+            assert!(is_private_host(&Url::parse(url).unwrap()), "{url}");
+        }
+    }
+
+    #[test]
+    fn allows_public_hosts() {
+        for url in [
+            "https://example.com/",
+            "http://93.184.216.34/",
+            "http://[2606:4700::1111]/",
+        ] {
+            assert!(!is_private_host(&Url::parse(url).unwrap()), "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn resolver_rejects_names_resolving_to_loopback() {
+        let result = PublicResolver
+            .resolve(Name::from_str("localhost").unwrap())
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn client_rejects_hostname_resolving_to_loopback() {
+        let app = Router::new().route("/", get(|| async { "ok" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        // 绕过主机名检查，只验证解析后的地址检查
+        let url = format!("http://localhost:{port}/");
+
+        let open = Client::new();
+        assert!(open.get(&url).send().await.is_ok());
+
+        let guarded = Client::builder()
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .unwrap();
+        let err = guarded.get(&url).send().await.unwrap_err();
+        assert!(format!("{err:?}").contains("private address"), "{err:?}");
+    }
+    #[tokio::test]
+    async fn retries_server_errors_through_outbound_client() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let app = Router::new().route(
+            "/",
+            get(move || {
+                let counter = counter.clone();
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                        (StatusCode::SERVICE_UNAVAILABLE, "busy").into_response()
+                    } else {
+                        ([(CONTENT_TYPE, "text/plain")], "hello").into_response()
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let fetcher = PageFetcher::from_config(FetchConfig {
+            allow_private_hosts: true,
+            ..FetchConfig::default()
+        })
+        .unwrap();
+        let page = fetcher.fetch(&url).await.unwrap();
+        assert_eq!(page.markdown, "hello");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(fetcher.http.circuit_status().len(), 1);
+    }
+}
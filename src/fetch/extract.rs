@@ -0,0 +1,724 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// 从 HTML 中提取出的正文与元信息
+#[derive(Debug, Clone, Default)]
+pub struct ExtractedArticle {
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub published_at: Option<String>,
+    pub canonical_url: Option<String>,
+    pub markdown: String,
+}
+
+/// 正文候选节点的 class/id 正向特征
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "text", "blog", "story",
+];
+/// 导航、评论、广告等与正文无关区域的 class/id 特征
+const NEGATIVE_HINTS: &[&str] = &[
+    "comment",
+    "footer",
+    "footnote",
+    "nav",
+    "navbar",
+    "navigation",
+    "menu",
+    "sidebar",
+    "share",
+    "social",
+    "related",
+    "ad",
+    "advert",
+    "advertisement",
+    "promo",
+    "banner",
+    "cookie",
+    "newsletter",
+    "subscribe",
+    "breadcrumb",
+    "popup",
+    "modal",
+    "masthead",
+];
+/// 不参与正文输出的标签
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "nav", "footer", "aside", "form", "button", "input", "select",
+    "textarea", "iframe", "svg", "canvas", "template", "object", "embed", "dialog",
+];
+
+/// 提取页面元信息与主体正文，并将正文转换为 Markdown
+///
+/// 正文定位采用 Readability 的段落打分思路：按段落文本长度与标点数量给父节点和祖父节点加分，
+/// 再结合 class/id 特征与链接密度选出得分最高的节点。
+pub fn extract_article(html: &str, base_url: &Url) -> ExtractedArticle {
+    let document = Html::parse_document(html);
+    let json_ld = json_ld_objects(&document);
+
+    let title = meta_content(&document, "meta[property='og:title']")
+        .or_else(|| first_text(&document, "title"))
+        .or_else(|| first_text(&document, "h1"));
+    let byline = meta_content(&document, "meta[name='author']")
+        .or_else(|| {
+            meta_content(&document, "meta[property='article:author']")
+                .filter(|value| !value.starts_with("http"))
+        })
+        .or_else(|| json_ld_author(&json_ld))
+        .or_else(|| first_text(&document, "[rel='author'], [itemprop='author'], .byline"))
+        .filter(|value| value.chars().count() <= 100);
+    let published_at = meta_content(&document, "meta[property='article:published_time']")
+        .or_else(|| {
+            meta_content(
+                &document,
+                "meta[name='pubdate'], meta[name='publishdate'], meta[name='date'], meta[name='dc.date'], meta[name='DC.date.issued'], meta[itemprop='datePublished']",
+            )
+        })
+        .or_else(|| json_ld_string(&json_ld, "datePublished"))
+        .or_else(|| first_attr(&document, "time[datetime]", "datetime"));
+    let canonical_url = first_attr(&document, "link[rel='canonical']", "href")
+        .or_else(|| meta_content(&document, "meta[property='og:url']"))
+        .and_then(|href| base_url.join(&href).ok())
+        .map(|url| url.to_string());
+
+    let root = find_content_root(&document).unwrap_or_else(|| document.root_element());
+    let mut writer = MarkdownWriter::new(base_url);
+    writer.write_children(root);
+
+    ExtractedArticle {
+        title,
+        byline,
+        published_at,
+        canonical_url,
+        markdown: writer.finish(),
+    }
+}
+
+fn find_content_root(document: &Html) -> Option<ElementRef<'_>> {
+    let paragraphs = selector("p, pre");
+    let mut scores = HashMap::new();
+    let mut candidates = HashMap::new();
+
+    for paragraph in document.select(&paragraphs) {
+        if has_skipped_ancestor(paragraph) {
+            continue;
+        }
+        let text = paragraph.text().collect::<String>();
+        let length = text.trim().chars().count();
+        if length < 25 {
+            continue;
+        }
+        let commas = text
+            .chars()
+            .filter(|ch| matches!(ch, ',' | '，' | '。' | '、'))
+            .count();
+        let score = 1.0 + commas as f64 + (length / 100).min(3) as f64;
+
+        let parent = paragraph.parent().and_then(ElementRef::wrap);
+        let grandparent = parent.and_then(|parent| parent.parent().and_then(ElementRef::wrap));
+        for (ancestor, weight) in [(parent, 1.0), (grandparent, 0.5)] {
+            if let Some(ancestor) = ancestor {
+                *scores.entry(ancestor.id()).or_insert(0.0) += score * weight;
+                candidates.entry(ancestor.id()).or_insert(ancestor);
+            }
+        }
+    }
+
+    let best = candidates
+        .into_iter()
+        .map(|(id, element)| {
+            let score = (scores[&id] + class_weight(element)) * (1.0 - link_density(element));
+            (score, element)
+        })
+        .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, element)| element);
+
+    best.or_else(|| {
+        document
+            .select(&selector("article, main, [role='main'], body"))
+            .next()
+    })
+}
+
+fn class_weight(element: ElementRef<'_>) -> f64 {
+    let tokens = hint_tokens(element);
+    let mut weight = 0.0;
+    if matches_hint(&tokens, POSITIVE_HINTS) {
+        weight += 25.0;
+    }
+    if matches_hint(&tokens, NEGATIVE_HINTS) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+fn link_density(element: ElementRef<'_>) -> f64 {
+    let total = element
+        .text()
+        .map(|text| text.chars().count())
+        .sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let links = element
+        .select(&selector("a"))
+        .flat_map(|link| link.text())
+        .map(|text| text.chars().count())
+        .sum::<usize>();
+    (links as f64 / total as f64).min(1.0)
+}
+
+/// class 与 id 按空白、`-`、`_` 切分后的小写片段
+fn hint_tokens(element: ElementRef<'_>) -> Vec<String> {
+    let value = element.value();
+    [value.attr("class"), value.attr("id")]
+        .into_iter()
+        .flatten()
+        .flat_map(|attr| attr.split(|ch: char| ch.is_whitespace() || ch == '-' || ch == '_'))
+        .filter(|token| !token.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// 片段与特征词完全相同（允许复数形式）时视为命中，避免 `canvas` 之类的词因包含 `nav` 被误判
+fn matches_hint(tokens: &[String], hints: &[&str]) -> bool {
+    tokens.iter().any(|token| {
+        let singular = token.strip_suffix('s').unwrap_or(token);
+        hints.contains(&token.as_str()) || hints.contains(&singular)
+    })
+}
+
+/// 判断元素是否为与正文无关的区域（导航、评论、隐藏元素等）
+fn is_boilerplate(element: ElementRef<'_>) -> bool {
+    let value = element.value();
+    if SKIPPED_TAGS.contains(&value.name()) {
+        return true;
+    }
+    if value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value
+            .attr("style")
+            .is_some_and(|style| style.replace(' ', "").contains("display:none"))
+    {
+        return true;
+    }
+    let tokens = hint_tokens(element);
+    matches_hint(&tokens, NEGATIVE_HINTS) && !matches_hint(&tokens, POSITIVE_HINTS)
+}
+
+fn has_skipped_ancestor(element: ElementRef<'_>) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(is_boilerplate)
+}
+
+/// 将 HTML 节点树写成 Markdown
+struct MarkdownWriter<'a> {
+    base_url: &'a Url,
+    out: String,
+}
+
+impl<'a> MarkdownWriter<'a> {
+    fn new(base_url: &'a Url) -> Self {
+        Self {
+            base_url,
+            out: String::new(),
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut result = String::with_capacity(self.out.len());
+        let mut blank_lines = 0;
+        for line in self.out.lines() {
+            let line = line.trim_end();
+            if line.trim().is_empty() {
+                blank_lines += 1;
+                if blank_lines > 1 {
+                    continue;
+                }
+            } else {
+                blank_lines = 0;
+            }
+            result.push_str(line);
+            result.push('\n');
+        }
+        result.trim().to_string()
+    }
+
+    fn write_children(&mut self, element: ElementRef<'_>) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.write_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.write_element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn write_text(&mut self, text: &str) {
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let starts_with_space = text.starts_with(char::is_whitespace);
+        let ends_with_space = text.ends_with(char::is_whitespace);
+        if collapsed.is_empty() {
+            if starts_with_space {
+                self.push_space();
+            }
+            return;
+        }
+        if starts_with_space {
+            self.push_space();
+        }
+        self.out.push_str(&collapsed);
+        if ends_with_space {
+            self.out.push(' ');
+        }
+    }
+
+    fn push_space(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with(char::is_whitespace) {
+            self.out.push(' ');
+        }
+    }
+
+    fn block_break(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push_str(if self.out.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+    }
+
+    fn write_element(&mut self, element: ElementRef<'_>) {
+        if is_boilerplate(element) {
+            return;
+        }
+
+        match element.value().name() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = element.value().name()[1..].parse::<usize>().unwrap_or(1);
+                let text = self.render_inline(element);
+                if !text.is_empty() {
+                    self.block_break();
+                    self.out.push_str(&"#".repeat(level));
+                    self.out.push(' ');
+                    self.out.push_str(&text);
+                    self.block_break();
+                }
+            }
+            "p" | "div" | "section" | "article" | "main" | "header" | "figure" | "figcaption"
+            | "dl" | "dd" | "dt" | "details" | "summary" => {
+                self.block_break();
+                self.write_children(element);
+                self.block_break();
+            }
+            "br" => self.out.push('\n'),
+            "hr" => {
+                self.block_break();
+                self.out.push_str("---");
+                self.block_break();
+            }
+            "a" => self.write_link(element),
+            "img" => self.write_image(element),
+            "strong" | "b" => self.write_wrapped(element, "**"),
+            "em" | "i" => self.write_wrapped(element, "*"),
+            "del" | "s" => self.write_wrapped(element, "~~"),
+            "code" => {
+                let code = element.text().collect::<String>();
+                if !code.trim().is_empty() {
+                    self.out.push('`');
+                    self.out.push_str(code.trim());
+                    self.out.push('`');
+                }
+            }
+            "pre" => self.write_code_block(element),
+            "blockquote" => {
+                let quoted = self.render_block(element);
+                if !quoted.is_empty() {
+                    self.block_break();
+                    for line in quoted.lines() {
+                        self.out.push_str("> ");
+                        self.out.push_str(line);
+                        self.out.push('\n');
+                    }
+                    self.block_break();
+                }
+            }
+            "ul" | "ol" => self.write_list(element),
+            "table" => self.write_table(element),
+            _ => self.write_children(element),
+        }
+    }
+
+    fn write_wrapped(&mut self, element: ElementRef<'_>, marker: &str) {
+        let text = self.render_inline(element);
+        if text.is_empty() {
+            return;
+        }
+        self.push_space_if_word_before();
+        self.out.push_str(marker);
+        self.out.push_str(&text);
+        self.out.push_str(marker);
+    }
+
+    fn push_space_if_word_before(&mut self) {
+        if self.out.ends_with(|ch: char| ch.is_alphanumeric()) {
+            self.out.push(' ');
+        }
+    }
+
+    fn write_link(&mut self, element: ElementRef<'_>) {
+        let text = self.render_inline(element);
+        let href = element
+            .value()
+            .attr("href")
+            .map(str::trim)
+            .filter(|href| !href.is_empty() && !href.starts_with('#'))
+            .filter(|href| !href.to_ascii_lowercase().starts_with("javascript:"))
+            .and_then(|href| self.base_url.join(href).ok());
+
+        match (href, text.is_empty()) {
+            (_, true) => {}
+            (Some(href), false) => {
+                self.push_space_if_word_before();
+                self.out.push_str(&format!("[{}]({})", text, href));
+            }
+            (None, false) => self.out.push_str(&text),
+        }
+    }
+
+    fn write_image(&mut self, element: ElementRef<'_>) {
+        let value = element.value();
+        let Some(src) = value
+            .attr("src")
+            .or_else(|| value.attr("data-src"))
+            .filter(|src| !src.starts_with("data:"))
+            .and_then(|src| self.base_url.join(src.trim()).ok())
+        else {
+            return;
+        };
+        let alt = value.attr("alt").unwrap_or_default().trim();
+        self.out.push_str(&format!("![{}]({})", alt, src));
+    }
+
+    fn write_code_block(&mut self, element: ElementRef<'_>) {
+        let code = element.text().collect::<String>();
+        if code.trim().is_empty() {
+            return;
+        }
+        let language = element
+            .select(&selector("code"))
+            .next()
+            .and_then(|code| code.value().attr("class"))
+            .or_else(|| element.value().attr("class"))
+            .and_then(|class| {
+                class
+                    .split_whitespace()
+                    .find_map(|name| name.strip_prefix("language-"))
+            })
+            .unwrap_or_default();
+
+        self.block_break();
+        self.out.push_str("```");
+        self.out.push_str(language);
+        self.out.push('\n');
+        self.out.push_str(code.trim_matches('\n'));
+        self.out.push_str("\n```");
+        self.block_break();
+    }
+
+    fn write_list(&mut self, element: ElementRef<'_>) {
+        let ordered = element.value().name() == "ol";
+        let mut index = 1;
+        self.block_break();
+        for item in element.children().filter_map(ElementRef::wrap) {
+            if item.value().name() != "li" || is_boilerplate(item) {
+                continue;
+            }
+            let content = self.render_block(item);
+            if content.is_empty() {
+                continue;
+            }
+            let marker = if ordered {
+                format!("{}. ", index)
+            } else {
+                "- ".to_string()
+            };
+            let indent = " ".repeat(marker.len());
+            let lines = content.lines().filter(|line| !line.trim().is_empty());
+            for (line_index, line) in lines.enumerate() {
+                if line_index == 0 {
+                    self.out.push_str(&marker);
+                } else {
+                    self.out.push_str(&indent);
+                }
+                self.out.push_str(line);
+                self.out.push('\n');
+            }
+            index += 1;
+        }
+        self.block_break();
+    }
+
+    fn write_table(&mut self, element: ElementRef<'_>) {
+        let rows: Vec<Vec<String>> = element
+            .select(&selector("tr"))
+            .map(|row| {
+                row.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                    .map(|cell| self.render_inline(cell).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect();
+        let Some(columns) = rows.iter().map(Vec::len).max() else {
+            return;
+        };
+
+        self.block_break();
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells = row.clone();
+            cells.resize(columns, String::new());
+            self.out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if index == 0 {
+                self.out
+                    .push_str(&format!("|{}\n", " --- |".repeat(columns)));
+            }
+        }
+        self.block_break();
+    }
+
+    /// 将元素内容渲染为单行文本，用于标题、链接文字和表格单元格
+    fn render_inline(&self, element: ElementRef<'_>) -> String {
+        self.render_block(element)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn render_block(&self, element: ElementRef<'_>) -> String {
+        let mut writer = MarkdownWriter::new(self.base_url);
+        writer.write_children(element);
+        writer.finish()
+    }
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+fn clean_text(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+fn meta_content(document: &Html, css: &str) -> Option<String> {
+    first_attr(document, css, "content")
+}
+
+fn first_attr(document: &Html, css: &str, attr: &str) -> Option<String> {
+    document
+        .select(&selector(css))
+        .filter_map(|element| element.value().attr(attr))
+        .find_map(clean_text)
+}
+
+fn first_text(document: &Html, css: &str) -> Option<String> {
+    document
+        .select(&selector(css))
+        .find_map(|element| clean_text(&element.text().collect::<String>()))
+}
+
+/// 收集页面中 JSON-LD 声明的所有对象（展开数组与 `@graph`）
+fn json_ld_objects(document: &Html) -> Vec<serde_json::Value> {
+    let mut objects = Vec::new();
+    let mut pending: Vec<serde_json::Value> = document
+        .select(&selector("script[type='application/ld+json']"))
+        .filter_map(|script| serde_json::from_str(&script.text().collect::<String>()).ok())
+        .collect();
+
+    while let Some(value) = pending.pop() {
+        match value {
+            serde_json::Value::Array(items) => pending.extend(items),
+            serde_json::Value::Object(mut object) => {
+                if let Some(graph) = object.remove("@graph") {
+                    pending.push(graph);
+                }
+                objects.push(serde_json::Value::Object(object));
+            }
+            _ => {}
+        }
+    }
+    objects
+}
+
+fn json_ld_string(objects: &[serde_json::Value], key: &str) -> Option<String> {
+    objects
+        .iter()
+        .filter_map(|object| object.get(key))
+        .filter_map(|value| value.as_str())
+        .find_map(clean_text)
+}
+
+fn json_ld_author(objects: &[serde_json::Value]) -> Option<String> {
+    objects
+        .iter()
+        .filter_map(|object| object.get("author"))
+        .flat_map(|author| match author {
+            serde_json::Value::Array(items) => items.iter().collect::<Vec<_>>(),
+            other => vec![other],
+        })
+        .filter_map(|author| match author {
+            serde_json::Value::String(name) => Some(name.as_str()),
+            serde_json::Value::Object(object) => object.get("name").and_then(|name| name.as_str()),
+            _ => None,
+        })
+        .find_map(clean_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = r##"<html><head>
+<title>Fallback title</title>
+<meta property="og:title" content="Rust Async Guide">
+<link rel="canonical" href="/guides/async">
+<script type="application/ld+json">
+{"@context": "https://schema.org", "@graph": [
+  {"@type": "WebSite", "name": "Example"},
+  {"@type": "Article", "author": [{"@type": "Person", "name": "Jane Doe"}],
+   "datePublished": "2024-03-01T08:00:00Z"}
+]}
+</script>
+</head><body>
+<nav><a href="/">Home</a> <a href="/docs">Docs</a></nav>
+<div class="menu-wrapper">Menu item</div>
+<article class="post-content">
+<h1>Rust Async Guide</h1>
+<p>Async Rust lets you write concurrent code, without threads, using futures and an executor such as Tokio.</p>
+<p>Read the <a href="../book/intro.html">introduction</a> and the <a href="https://tokio.rs/">Tokio site</a>, or jump to <a href="#setup">setup</a>.</p>
+<div class="canvas-figure"><p>This figure explains, in some detail, how the executor polls futures to completion.</p></div>
+<ul><li>First item</li><li>Second <strong>item</strong></li></ul>
+<ol><li>Install</li><li>Run</li></ol>
+<table><tr><th>Crate</th><th>Use</th></tr><tr><td>tokio</td><td>runtime</td></tr><tr><td>a|b</td></tr></table>
+<pre><code class="language-rust">fn main() {
+    println!("hi");
+}</code></pre>
+</article>
+<div id="comments"><p>Great article, thanks a lot for writing this, it was very helpful!</p></div>
+<footer>Copyright 2024 Example</footer>
+</body></html>"##;
+
+    fn extract(html: &str) -> ExtractedArticle {
+        let base_url = Url::parse("https://example.com/blog/posts/async.html").unwrap();
+        extract_article(html, &base_url)
+    }
+
+    #[test]
+    fn removes_navigation_comments_and_footer() {
+        let markdown = extract(ARTICLE).markdown;
+        assert!(markdown.starts_with("# Rust Async Guide"), "{markdown}");
+        for boilerplate in ["Home", "Menu item", "Great article", "Copyright"] {
+            assert!(!markdown.contains(boilerplate), "{boilerplate}: {markdown}");
+        }
+        // class 中包含 nav 子串的正文块不会被当作导航
+        assert!(
+            markdown.contains("how the executor polls futures"),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn resolves_relative_links() {
+        let markdown = extract(ARTICLE).markdown;
+        assert!(
+            markdown.contains("[introduction](https://example.com/blog/book/intro.html)"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("[Tokio site](https://tokio.rs/)"),
+            "{markdown}"
+        );
+        assert!(markdown.contains("jump to setup."), "{markdown}");
+    }
+
+    #[test]
+    fn reads_metadata_from_json_ld() {
+        let article = extract(ARTICLE);
+        assert_eq!(article.title.as_deref(), Some("Rust Async Guide"));
+        assert_eq!(article.byline.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            article.published_at.as_deref(),
+            Some("2024-03-01T08:00:00Z")
+        );
+        assert_eq!(
+            article.canonical_url.as_deref(),
+            Some("https://example.com/guides/async")
+        );
+    }
+
+    #[test]
+    fn reads_metadata_from_meta_tags() {
+        let article = extract(
+            r#"<html><head>
+<title> Page   title </title>
+<meta name="author" content="John Smith">
+<meta property="article:published_time" content="2023-01-02">
+<meta property="og:url" content="https://example.com/canonical">
+</head><body><p>Body text that is long enough to be picked as the content root.</p></body></html>"#,
+        );
+        assert_eq!(article.title.as_deref(), Some("Page title"));
+        assert_eq!(article.byline.as_deref(), Some("John Smith"));
+        assert_eq!(article.published_at.as_deref(), Some("2023-01-02"));
+        assert_eq!(
+            article.canonical_url.as_deref(),
+            Some("https://example.com/canonical")
+        );
+    }
+
+    #[test]
+    fn converts_lists_tables_and_code() {
+        let markdown = extract(ARTICLE).markdown;
+        assert!(
+            markdown.contains("- First item\n- Second **item**"),
+            "{markdown}"
+        );
+        assert!(markdown.contains("1. Install\n2. Run"), "{markdown}");
+        assert!(
+            markdown.contains("| Crate | Use |\n| --- | --- |\n| tokio | runtime |\n| a\\|b |  |"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("```rust\nfn main() {\n    println!(\"hi\");\n}\n```"),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn matches_whole_hint_segments() {
+        let tokens = |value: &str| -> Vec<String> {
+            value
+                .split(|ch: char| ch.is_whitespace() || ch == '-' || ch == '_')
+                .map(str::to_string)
+                .collect()
+        };
+        assert!(matches_hint(&tokens("site-nav"), NEGATIVE_HINTS));
+        assert!(matches_hint(&tokens("comments"), NEGATIVE_HINTS));
+        assert!(matches_hint(&tokens("ad_slot"), NEGATIVE_HINTS));
+        assert!(!matches_hint(&tokens("canvas"), NEGATIVE_HINTS));
+        assert!(!matches_hint(
+            &tokens("unavailable shared-state"),
+            NEGATIVE_HINTS
+        ));
+        assert!(matches_hint(&tokens("entry-content"), POSITIVE_HINTS));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod extract;
+pub mod types;
+
+pub use client::{FetchConfig, PageFetcher};
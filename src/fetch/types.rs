@@ -0,0 +1,95 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 抓取并提取正文后的网页
+#[derive(Debug, Clone)]
+pub struct FetchedPage {
+    /// 跟随重定向后的最终地址
+    pub final_url: String,
+    /// 页面声明的规范地址（`<link rel="canonical">` 或 `og:url`），未声明时为最终地址
+    pub canonical_url: String,
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub published_at: Option<String>,
+    /// Markdown 格式的正文，链接已转换为绝对地址
+    pub markdown: String,
+}
+
+impl FetchedPage {
+    /// 从第 `offset` 个字符开始截取最多 `max_chars` 个字符的正文
+    ///
+    /// 尽量在换行处截断，返回截取的内容以及继续读取时的偏移；已读完时偏移为空。
+    pub fn window(&self, offset: usize, max_chars: usize) -> (String, Option<usize>) {
+        let rest: Vec<char> = self.markdown.chars().skip(offset).collect();
+        if rest.len() <= max_chars {
+            return (rest.into_iter().collect(), None);
+        }
+
+        let mut end = max_chars;
+        if let Some(newline) = rest[..max_chars].iter().rposition(|ch| *ch == '\n')
+            && newline >= max_chars / 2
+        {
+            end = newline + 1;
+        }
+        (rest[..end].iter().collect(), Some(offset + end))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FetchUrlResponse {
+    pub success: bool,
+    pub url: String,
+    pub final_url: Option<String>,
+    pub canonical_url: Option<String>,
+    pub title: Option<String>,
+    pub byline: Option<String>,
+    pub published_at: Option<String>,
+    /// 从 offset 开始截取的 Markdown 正文
+    pub content: String,
+    /// 本次返回内容在全文中的起始字符偏移
+    pub offset: usize,
+    /// 正文总字符数
+    pub total_chars: usize,
+    /// 继续读取时使用的 offset；已读完时为空
+    pub next_offset: Option<usize>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(markdown: &str) -> FetchedPage {
+        FetchedPage {
+            final_url: "https://example.com/".to_string(),
+            canonical_url: "https://example.com/".to_string(),
+            title: None,
+            byline: None,
+            published_at: None,
+            markdown: markdown.to_string(),
+        }
+    }
+
+    #[test]
+    fn window_continues_at_line_breaks() {
+        let page = page("first line\nsecond line\nthird");
+        assert_eq!(page.window(0, 15), ("first line\n".to_string(), Some(11)));
+        assert_eq!(page.window(11, 15), ("second line\n".to_string(), Some(23)));
+        assert_eq!(page.window(23, 15), ("third".to_string(), None));
+    }
+
+    #[test]
+    fn window_cuts_mid_line_without_nearby_break() {
+        let page = page("a\nbcdefghij");
+        assert_eq!(page.window(0, 8), ("a\nbcdefg".to_string(), Some(8)));
+        assert_eq!(page.window(8, 8), ("hij".to_string(), None));
+    }
+
+    #[test]
+    fn window_counts_characters_not_bytes() {
+        let page = page("你好世界");
+        assert_eq!(page.window(1, 2), ("好世".to_string(), Some(3)));
+        assert_eq!(page.window(3, 2), ("界".to_string(), None));
+        assert_eq!(page.window(10, 2), (String::new(), None));
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+/// 熔断器状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 正常放行请求
+    Closed,
+    /// 连续失败过多，冷却期内直接拒绝请求
+    Open,
+    /// 冷却期结束，放行一个试探请求
+    HalfOpen,
+}
+
+/// 熔断器状态快照，用于健康检查输出
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitStatus {
+    /// 上游名称，如 `searxng`、`rerank`
+    pub upstream: String,
+    /// 上游主机，非默认端口时包含端口
+    pub host: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// 熔断打开时距离允许试探请求的剩余秒数
+    pub retry_in_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// 半开状态下是否已有试探请求在进行
+    probing: bool,
+}
+
+/// 按上游主机划分的熔断器
+///
+/// 连续失败达到阈值后打开，冷却期结束后进入半开状态放行一个试探请求，
+/// 试探成功则关闭，失败则重新打开。
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    upstream: &'static str,
+    host: String,
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(
+        upstream: &'static str,
+        host: String,
+        failure_threshold: u32,
+        cooldown: Duration,
+    ) -> Self {
+        Self {
+            upstream,
+            host,
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probing: false,
+            }),
+        }
+    }
+
+    /// 判断是否放行本次请求，放行时返回用于记录结果的许可
+    ///
+    /// 半开状态下的许可即试探请求；许可未记录结果就被丢弃（如请求被取消）时释放试探名额，
+    /// 避免熔断器永远停留在半开状态。
+    pub fn allow(&self) -> Option<BreakerPermit<'_>> {
+        let mut inner = self.inner.lock().unwrap();
+        let probe = match inner.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => {
+                let cooled = inner
+                    .opened_at
+                    .is_some_and(|opened_at| opened_at.elapsed() >= self.cooldown);
+                if !cooled {
+                    return None;
+                }
+                info!(upstream = self.upstream, host = %self.host, "Circuit half-open, sending probe request");
+                inner.state = CircuitState::HalfOpen;
+                inner.probing = true;
+                true
+            }
+            CircuitState::HalfOpen => {
+                if inner.probing {
+                    return None;
+                }
+                inner.probing = true;
+                true
+            }
+        };
+        Some(BreakerPermit {
+            breaker: self,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != CircuitState::Closed {
+            info!(upstream = self.upstream, host = %self.host, "Circuit closed");
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probing = false;
+    }
+
+    fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.probing = false;
+        let should_open = match inner.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => inner.consecutive_failures >= self.failure_threshold,
+            CircuitState::Open => false,
+        };
+        if should_open {
+            warn!(
+                upstream = self.upstream,
+                host = %self.host,
+                failures = inner.consecutive_failures,
+                cooldown_secs = self.cooldown.as_secs(),
+                "Circuit opened"
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    /// 试探请求未完成就被取消，允许下一个请求重新试探
+    fn release_probe(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state == CircuitState::HalfOpen && inner.probing {
+            debug!(upstream = self.upstream, host = %self.host, "Circuit probe cancelled");
+            inner.probing = false;
+        }
+    }
+
+    pub fn status(&self) -> CircuitStatus {
+        let inner = self.inner.lock().unwrap();
+        let retry_in_secs = match (inner.state, inner.opened_at) {
+            (CircuitState::Open, Some(opened_at)) => {
+                Some(self.cooldown.saturating_sub(opened_at.elapsed()).as_secs())
+            }
+            _ => None,
+        };
+        CircuitStatus {
+            upstream: self.upstream.to_string(),
+            host: self.host.clone(),
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            retry_in_secs,
+        }
+    }
+}
+
+/// 熔断器放行的单次请求许可，须以 [`BreakerPermit::success`] 或 [`BreakerPermit::failure`] 记录结果
+#[must_use]
+pub struct BreakerPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl BreakerPermit<'_> {
+    pub fn success(mut self) {
+        self.recorded = true;
+        self.breaker.record_success();
+    }
+
+    pub fn failure(mut self) {
+        self.recorded = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for BreakerPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.breaker.release_probe();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker::new("test", "example.com".to_string(), 2, cooldown)
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = breaker(Duration::from_secs(60));
+        breaker.allow().unwrap().failure();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        breaker.allow().unwrap().failure();
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(breaker.allow().is_none());
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let breaker = breaker(Duration::from_secs(60));
+        breaker.allow().unwrap().failure();
+        breaker.allow().unwrap().success();
+        breaker.allow().unwrap().failure();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn half_open_probe_closes_on_success() {
+        let breaker = breaker(Duration::ZERO);
+        breaker.allow().unwrap().failure();
+        breaker.allow().unwrap().failure();
+        assert_eq!(breaker.status().state, CircuitState::Open);
+
+        let probe = breaker.allow().expect("cooldown elapsed");
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        // 同一时间只放行一个试探请求
+        assert!(breaker.allow().is_none());
+        probe.success();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+        assert!(breaker.allow().is_some());
+    }
+
+    #[test]
+    fn half_open_probe_reopens_on_failure() {
+        let breaker = breaker(Duration::from_secs(60));
+        {
+            let mut inner = breaker.inner.lock().unwrap();
+            inner.state = CircuitState::HalfOpen;
+        }
+        breaker.allow().unwrap().failure();
+        assert_eq!(breaker.status().state, CircuitState::Open);
+        assert!(breaker.allow().is_none());
+    }
+
+    #[test]
+    fn cancelled_probe_allows_next_probe() {
+        let breaker = breaker(Duration::ZERO);
+        breaker.allow().unwrap().failure();
+        breaker.allow().unwrap().failure();
+
+        let probe = breaker.allow().expect("cooldown elapsed");
+        assert!(breaker.allow().is_none());
+        drop(probe);
+        assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+        breaker.allow().expect("probe released").success();
+        assert_eq!(breaker.status().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn dropped_closed_permit_keeps_state() {
+        let breaker = breaker(Duration::from_secs(60));
+        drop(breaker.allow().unwrap());
+        let status = breaker.status();
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.consecutive_failures, 0);
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use reqwest::{
+    Client, ClientBuilder, Method, RequestBuilder, Response, StatusCode, header::RETRY_AFTER,
+};
+use tracing::{debug, warn};
+
+use super::breaker::{CircuitBreaker, CircuitStatus};
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 20;
+pub const DEFAULT_MAX_RETRIES: u32 = 2;
+pub const DEFAULT_BREAKER_THRESHOLD: u32 = 5;
+pub const DEFAULT_BREAKER_COOLDOWN_SECS: u64 = 30;
+const BASE_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+/// `Retry-After` 超过该值时不再等待，直接返回
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// 单个上游的出站 HTTP 配置
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub connect_timeout: Duration,
+    /// 单次请求（含读取响应体）的总超时
+    pub request_timeout: Duration,
+    /// 首次请求失败后的最大重试次数
+    pub max_retries: u32,
+    /// 连续失败多少次后打开熔断器
+    pub breaker_threshold: u32,
+    /// 熔断器打开后的冷却时间
+    pub breaker_cooldown: Duration,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            max_retries: DEFAULT_MAX_RETRIES,
+            breaker_threshold: DEFAULT_BREAKER_THRESHOLD,
+            breaker_cooldown: Duration::from_secs(DEFAULT_BREAKER_COOLDOWN_SECS),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// 使用默认重试与熔断参数，仅指定请求超时
+    pub fn with_timeout(request_timeout: Duration) -> Self {
+        Self {
+            request_timeout,
+            ..Self::default()
+        }
+    }
+}
+
+/// 带超时、重试与熔断的出站 HTTP 客户端
+///
+/// 幂等请求（GET/HEAD 或通过 [`OutboundClient::send_idempotent`] 发送的请求）在连接失败、
+/// 超时与 429/5xx 时按带抖动的指数退避重试，并遵循 `Retry-After`；非幂等请求只在连接
+/// 建立失败或 429 时重试。熔断器按上游主机（含端口）划分。
+#[derive(Clone)]
+pub struct OutboundClient {
+    upstream: &'static str,
+    http: Client,
+    config: HttpConfig,
+    breakers: Arc<Mutex<HashMap<String, Arc<CircuitBreaker>>>>,
+}
+
+impl OutboundClient {
+    /// `builder` 用于设置 User-Agent 等上游特有选项，超时由 `config` 覆盖
+    pub fn new(upstream: &'static str, config: HttpConfig, builder: ClientBuilder) -> Result<Self> {
+        let http = builder
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.request_timeout)
+            .build()
+            .with_context(|| format!("build {upstream} http client failed"))?;
+        Ok(Self {
+            upstream,
+            http,
+            config,
+            breakers: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// 底层 reqwest 客户端，用于构造请求或绕过重试与熔断（如健康探测）
+    pub fn inner(&self) -> &Client {
+        &self.http
+    }
+
+    /// 发送请求，按请求方法判断是否幂等
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let idempotent = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .is_some_and(|request| matches!(*request.method(), Method::GET | Method::HEAD));
+        self.execute(request, idempotent).await
+    }
+
+    /// 发送没有副作用的请求（如重排序 POST），按幂等请求处理重试
+    pub async fn send_idempotent(&self, request: RequestBuilder) -> Result<Response> {
+        self.execute(request, true).await
+    }
+
+    pub fn circuit_status(&self) -> Vec<CircuitStatus> {
+        let breakers = self.breakers.lock().unwrap();
+        let mut status: Vec<CircuitStatus> =
+            breakers.values().map(|breaker| breaker.status()).collect();
+        status.sort_by(|a, b| a.host.cmp(&b.host));
+        status
+    }
+
+    async fn execute(&self, request: RequestBuilder, idempotent: bool) -> Result<Response> {
+        let host = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .and_then(|request| {
+                let url = request.url();
+                let host = url.host_str()?;
+                Some(match url.port() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host.to_string(),
+                })
+            })
+            .unwrap_or_default();
+        let breaker = self.breaker(&host);
+        // 许可在请求被取消（future 被丢弃）时自动释放半开状态的试探名额
+        let Some(permit) = breaker.allow() else {
+            anyhow::bail!(
+                "{} circuit open for {host}, request rejected",
+                self.upstream
+            );
+        };
+
+        let mut attempt = 0;
+        let mut pending = Some(request);
+        loop {
+            let current = pending.take().context("request already consumed")?;
+            // 请求体为流时无法克隆，此时不重试
+            let retry_request = current.try_clone();
+            let can_retry = attempt < self.config.max_retries && retry_request.is_some();
+
+            let (retry_after, outcome) = match current.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if !is_retryable_status(status) {
+                        // 4xx 为调用方问题，不计入上游故障
+                        permit.success();
+                        return Ok(response);
+                    }
+                    let retry_after = retry_after(&response);
+                    let retry =
+                        can_retry && (idempotent || status == StatusCode::TOO_MANY_REQUESTS);
+                    if !retry {
+                        permit.failure();
+                        return Ok(response);
+                    }
+                    (retry_after, format!("status {status}"))
+                }
+                Err(err) => {
+                    let retry = can_retry && (idempotent || err.is_connect());
+                    if !retry {
+                        permit.failure();
+                        return Err(err.into());
+                    }
+                    (None, format!("{err}"))
+                }
+            };
+
+            let delay = match retry_after {
+                Some(delay) if delay > MAX_RETRY_AFTER => {
+                    permit.failure();
+                    anyhow::bail!(
+                        "{} asked to retry after {}s, giving up",
+                        self.upstream,
+                        delay.as_secs()
+                    );
+                }
+                Some(delay) => delay,
+                None => backoff(attempt),
+            };
+            attempt += 1;
+            warn!(
+                upstream = self.upstream,
+                host = %host,
+                attempt,
+                max_retries = self.config.max_retries,
+                delay_ms = delay.as_millis() as u64,
+                reason = %outcome,
+                "Retrying outbound request"
+            );
+            tokio::time::sleep(delay).await;
+            pending = retry_request;
+        }
+    }
+
+    fn breaker(&self, host: &str) -> Arc<CircuitBreaker> {
+        let mut breakers = self.breakers.lock().unwrap();
+        breakers
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                debug!(upstream = self.upstream, host, "Creating circuit breaker");
+                Arc::new(CircuitBreaker::new(
+                    self.upstream,
+                    host.to_string(),
+                    self.config.breaker_threshold,
+                    self.config.breaker_cooldown,
+                ))
+            })
+            .clone()
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// 解析以秒为单位的 `Retry-After`，HTTP 日期格式按未设置处理
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// 带完全抖动的指数退避：`[0, min(MAX_BACKOFF, BASE_BACKOFF * 2^attempt)]`
+fn backoff(attempt: u32) -> Duration {
+    let ceiling = BASE_BACKOFF
+        .saturating_mul(1 << attempt.min(10))
+        .min(MAX_BACKOFF);
+    ceiling.mul_f64(fastrand::f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    use axum::{Router, extract::State, http::StatusCode as AxumStatus, routing::get};
+
+    use super::*;
+    use crate::http::CircuitState;
+
+    const FAIL: u8 = 0;
+    const HANG: u8 = 1;
+    const OK: u8 = 2;
+
+    async fn handler(State(mode): State<Arc<AtomicU8>>) -> AxumStatus {
+        match mode.load(Ordering::SeqCst) {
+            FAIL => AxumStatus::INTERNAL_SERVER_ERROR,
+            HANG => {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                AxumStatus::OK
+            }
+            _ => AxumStatus::OK,
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_probe_does_not_block_circuit() {
+        let mode = Arc::new(AtomicU8::new(FAIL));
+        let app = Router::new()
+            .route("/", get(handler))
+            .with_state(mode.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let config = HttpConfig {
+            max_retries: 0,
+            breaker_threshold: 1,
+            breaker_cooldown: Duration::ZERO,
+            ..HttpConfig::default()
+        };
+        let client = OutboundClient::new("test", config, Client::builder()).unwrap();
+
+        let response = client.send(client.inner().get(&url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(client.circuit_status()[0].state, CircuitState::Open);
+
+        // 试探请求在完成前被取消
+        mode.store(HANG, Ordering::SeqCst);
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(100),
+            client.send(client.inner().get(&url)),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        mode.store(OK, Ordering::SeqCst);
+        let response = client.send(client.inner().get(&url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(client.circuit_status()[0].state, CircuitState::Closed);
+    }
+}
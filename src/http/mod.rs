@@ -0,0 +1,5 @@
+pub mod breaker;
+pub mod client;
+
+pub use breaker::{CircuitState, CircuitStatus};
+pub use client::{HttpConfig, OutboundClient};
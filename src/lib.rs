@@ -1,5 +1,9 @@
 pub mod cratesio;
+pub mod fetch;
+pub mod http;
+pub mod llm;
 pub mod mcp;
 pub mod rerank;
+pub mod research;
 pub mod search;
 pub mod searxng;
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use reqwest::Client;
+use tracing::{debug, info};
+
+use super::types::{ChatCompletionRequest, ChatCompletionResponse, ChatMessage};
+use crate::http::{HttpConfig, OutboundClient};
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+const ANSWER_TEMPERATURE: f32 = 0.2;
+
+/// OpenAI 兼容的 chat completions 客户端配置
+#[derive(Debug, Clone)]
+pub struct LlmConfig {
+    /// API 根地址，如 `https://api.openai.com/v1`，请求发送到 `{base_url}/chat/completions`
+    pub base_url: String,
+    /// 本地或无鉴权的服务可不设置
+    pub api_key: Option<String>,
+    pub model: String,
+    /// 超时、重试与熔断配置；生成请求不是幂等的，只在连接失败或 429 时重试
+    pub http: HttpConfig,
+    pub max_tokens: Option<u32>,
+}
+
+/// 生成结果
+#[derive(Debug, Clone)]
+pub struct ChatCompletion {
+    pub content: String,
+    /// 服务端实际使用的模型名称，未返回时为请求中的模型
+    pub model: String,
+}
+
+#[derive(Clone)]
+pub struct LlmClient {
+    endpoint: String,
+    api_key: Option<String>,
+    model: String,
+    max_tokens: Option<u32>,
+    http: OutboundClient,
+}
+
+impl LlmClient {
+    pub fn from_config(config: LlmConfig) -> Result<Self> {
+        let http = OutboundClient::new("llm", config.http, Client::builder())?;
+
+        Ok(Self {
+            endpoint: format!("{}/chat/completions", config.base_url.trim_end_matches('/')),
+            api_key: config.api_key,
+            model: config.model,
+            max_tokens: config.max_tokens,
+            http,
+        })
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub fn http(&self) -> &OutboundClient {
+        &self.http
+    }
+
+    pub async fn complete(&self, messages: Vec<ChatMessage>) -> Result<ChatCompletion> {
+        debug!(
+            endpoint = %self.endpoint,
+            model = %self.model,
+            messages_count = messages.len(),
+            "Sending chat completion request"
+        );
+
+        let request = ChatCompletionRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: ANSWER_TEMPERATURE,
+            max_tokens: self.max_tokens,
+        };
+
+        let mut builder = self.http.inner().post(&self.endpoint).json(&request);
+        if let Some(api_key) = &self.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+        let response = self
+            .http
+            .send(builder)
+            .await
+            .context("Failed to send chat completion request")?
+            .error_for_status()
+            .context("Chat completion API returned error status")?
+            .json::<ChatCompletionResponse>()
+            .await
+            .context("Failed to decode chat completion response")?;
+
+        let content = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .map(|content| content.trim().to_string())
+            .filter(|content| !content.is_empty())
+            .context("chat completion returned no content")?;
+        let model = response.model.unwrap_or_else(|| self.model.clone());
+
+        info!(model = %model, chars = content.len(), "Received chat completion");
+        Ok(ChatCompletion { content, model })
+    }
+}
@@ -1,29 +1,355 @@
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
 use anyhow::{Context, Result};
 
+use crate::{
+    fetch::{FetchConfig, client::DEFAULT_TIMEOUT_SECS as DEFAULT_FETCH_TIMEOUT_SECS},
+    http::{
+        HttpConfig,
+        client::{
+            DEFAULT_BREAKER_COOLDOWN_SECS, DEFAULT_BREAKER_THRESHOLD, DEFAULT_CONNECT_TIMEOUT_SECS,
+            DEFAULT_MAX_RETRIES, DEFAULT_REQUEST_TIMEOUT_SECS,
+        },
+    },
+    llm::{LlmConfig, client::DEFAULT_TIMEOUT_SECS as DEFAULT_LLM_TIMEOUT_SECS},
+    rerank::{
+        QueryTemplates, RerankConfig, RerankProvider, batch::DEFAULT_MAX_DOCUMENT_TOKENS,
+        cache::DEFAULT_CAPACITY as DEFAULT_RERANK_CACHE_CAPACITY, client::DEFAULT_TIMEOUT_SECS,
+    },
+    searxng::{
+        cache::{CacheConfig, CacheTtls},
+        pool::BalanceStrategy,
+    },
+};
+
+const DEFAULT_SEARCH_CONCURRENCY: usize = 4;
+const DEFAULT_HEALTH_CHECK_SECS: u64 = 30;
+
 #[derive(Debug, Clone)]
 pub struct McpConfig {
     pub bind: String,
-    pub searxng_url: String,
+    /// searXNG 实例地址列表，请求失败时按顺序切换
+    pub searxng_urls: Vec<String>,
+    /// 多个 searXNG 实例之间的选择策略
+    pub searxng_balance: BalanceStrategy,
+    /// 主动健康探测间隔；为 `None` 时不探测
+    pub searxng_health_check_interval: Option<Duration>,
+    /// 请求 searXNG 的超时、重试与熔断配置
+    pub searxng_http: HttpConfig,
     pub auth_token: Option<String>,
+    /// 多 query 搜索时的最大并发数
+    pub search_concurrency: usize,
+    /// 搜索结果缓存配置；为 `None` 时不缓存
+    pub search_cache: Option<CacheConfig>,
+    /// 访问 crates.io 时使用的 User-Agent，未设置时使用内置默认值
+    pub crates_io_user_agent: Option<String>,
+    /// 请求 crates.io 的超时、重试与熔断配置
+    pub crates_io_http: HttpConfig,
+    /// fetch_url 网页抓取配置
+    pub fetch: FetchConfig,
+    /// 重排序配置；为 `None` 时不启用重排序
+    pub rerank: Option<RerankConfig>,
+    /// 远程重排序失败时是否改用本地 BM25 排序
+    pub rerank_fallback: bool,
+    /// 重排序 query 模板
+    pub rerank_templates: QueryTemplates,
+    /// answer 工具使用的 LLM 配置；为 `None` 时 answer 只返回证据
+    pub llm: Option<LlmConfig>,
 }
 
 impl McpConfig {
     pub fn from_env() -> Result<Self> {
         let bind = std::env::var("MCP_BIND").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
-        let searxng_url = std::env::var("SEARXNG_URL").context("SEARXNG_URL is required")?;
-        let searxng_url = searxng_url.trim().trim_end_matches('/').to_string();
-        if searxng_url.is_empty() {
-            anyhow::bail!("SEARXNG_URL is required")
+        // SEARXNG_URLS 优先，两者均支持逗号分隔的多个地址
+        let searxng_urls: Vec<String> = optional_env("SEARXNG_URLS")
+            .or_else(|| optional_env("SEARXNG_URL"))
+            .unwrap_or_default()
+            .split(',')
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+        if searxng_urls.is_empty() {
+            anyhow::bail!("SEARXNG_URL or SEARXNG_URLS is required")
         }
-        let auth_token = std::env::var("MCP_AUTH_TOKEN")
-            .ok()
-            .map(|value| value.trim().to_string())
-            .filter(|value| !value.is_empty());
+        let searxng_balance =
+            parse_env::<BalanceStrategy>("SEARXNG_BALANCE")?.unwrap_or(BalanceStrategy::RoundRobin);
+        let health_check_secs =
+            parse_env::<u64>("SEARXNG_HEALTH_CHECK_SECS")?.unwrap_or(DEFAULT_HEALTH_CHECK_SECS);
+        let searxng_health_check_interval =
+            (health_check_secs > 0).then(|| Duration::from_secs(health_check_secs));
+        let auth_token = optional_env("MCP_AUTH_TOKEN");
+        let search_concurrency =
+            parse_env::<usize>("SEARCH_CONCURRENCY")?.unwrap_or(DEFAULT_SEARCH_CONCURRENCY);
+        if search_concurrency == 0 {
+            anyhow::bail!("SEARCH_CONCURRENCY must be greater than 0")
+        }
+        let search_cache = cache_config_from_env()?;
+        let searxng_http = http_config_from_env("SEARXNG", DEFAULT_REQUEST_TIMEOUT_SECS)?;
+        let crates_io_user_agent = optional_env("CRATES_IO_USER_AGENT");
+        let crates_io_http = http_config_from_env("CRATES_IO", DEFAULT_REQUEST_TIMEOUT_SECS)?;
+        let fetch = fetch_config_from_env()?;
+        let rerank = rerank_config_from_env()?;
+        let rerank_fallback = optional_env("RERANK_FALLBACK")
+            .map(|value| parse_bool("RERANK_FALLBACK", &value))
+            .transpose()?
+            .unwrap_or(true);
+        let rerank_templates = rerank_templates_from_env()?;
+        let llm = llm_config_from_env()?;
 
         Ok(Self {
             bind,
-            searxng_url,
+            searxng_urls,
+            searxng_balance,
+            searxng_health_check_interval,
+            searxng_http,
             auth_token,
+            search_concurrency,
+            search_cache,
+            crates_io_user_agent,
+            crates_io_http,
+            fetch,
+            rerank,
+            rerank_fallback,
+            rerank_templates,
+            llm,
+        })
+    }
+}
+
+/// 读取重排序 query 模板
+///
+/// `RERANK_QUERY_TEMPLATES_BY_CATEGORY` 与 `RERANK_QUERY_TEMPLATES_BY_LANGUAGE` 为 JSON 对象，
+/// 如 `{"news": "..."}`；分类模板的键可写作 `分类:语言`（如 `it:zh`），按语言的模板与内置英文模板合并。
+fn rerank_templates_from_env() -> Result<QueryTemplates> {
+    let mut templates = QueryTemplates::default();
+    if let Some(template) = optional_env("RERANK_QUERY_TEMPLATE") {
+        templates.default = template;
+    }
+    if let Some(value) = optional_env("RERANK_QUERY_TEMPLATES_BY_CATEGORY") {
+        let by_category = serde_json::from_str::<HashMap<String, String>>(&value)
+            .context("RERANK_QUERY_TEMPLATES_BY_CATEGORY must be a JSON object of strings")?;
+        templates.by_category = by_category
+            .into_iter()
+            .map(|(key, template)| match key.split_once(':') {
+                Some((category, language)) => (
+                    format!("{category}:{}", language.to_ascii_lowercase()),
+                    template,
+                ),
+                None => (key, template),
+            })
+            .collect();
+    }
+    if let Some(value) = optional_env("RERANK_QUERY_TEMPLATES_BY_LANGUAGE") {
+        let by_language = serde_json::from_str::<HashMap<String, String>>(&value)
+            .context("RERANK_QUERY_TEMPLATES_BY_LANGUAGE must be a JSON object of strings")?;
+        templates.by_language.extend(
+            by_language
+                .into_iter()
+                .map(|(language, template)| (language.to_ascii_lowercase(), template)),
+        );
+    }
+    Ok(templates)
+}
+
+/// 读取搜索缓存配置，`SEARCH_CACHE_ENABLED` 默认为 `true`
+///
+/// `SEARCH_CACHE_TTLS` 形如 `news=300,science=86400,default=3600`，单位为秒，
+/// 只覆盖列出的类别。
+fn cache_config_from_env() -> Result<Option<CacheConfig>> {
+    if let Some(value) = optional_env("SEARCH_CACHE_ENABLED")
+        && !parse_bool("SEARCH_CACHE_ENABLED", &value)?
+    {
+        return Ok(None);
+    }
+
+    let mut config = CacheConfig::default();
+    if let Some(capacity) = parse_env::<usize>("SEARCH_CACHE_CAPACITY")? {
+        if capacity == 0 {
+            anyhow::bail!("SEARCH_CACHE_CAPACITY must be greater than 0")
+        }
+        config.capacity = capacity;
+    }
+    config.path = optional_env("SEARCH_CACHE_PATH");
+    if let Some(value) = optional_env("SEARCH_CACHE_TTLS") {
+        config.ttls = parse_cache_ttls(&value)?;
+    }
+    Ok(Some(config))
+}
+
+fn parse_cache_ttls(value: &str) -> Result<CacheTtls> {
+    let mut ttls = CacheTtls::default();
+    for item in value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let (category, secs) = item
+            .split_once('=')
+            .with_context(|| format!("SEARCH_CACHE_TTLS entry must be `category=secs`: {item}"))?;
+        let secs = secs
+            .trim()
+            .parse::<u64>()
+            .with_context(|| format!("SEARCH_CACHE_TTLS has invalid seconds: {item}"))?;
+        let ttl = Duration::from_secs(secs);
+        match category.trim() {
+            "default" => ttls.default = ttl,
+            category => {
+                ttls.by_category.insert(category.to_string(), ttl);
+            }
+        }
+    }
+    Ok(ttls)
+}
+
+fn fetch_config_from_env() -> Result<FetchConfig> {
+    let mut config = FetchConfig {
+        http: http_config_from_env("FETCH", DEFAULT_FETCH_TIMEOUT_SECS)?,
+        ..FetchConfig::default()
+    };
+    if let Some(max_bytes) = parse_env::<usize>("FETCH_MAX_BYTES")? {
+        config.max_bytes = max_bytes;
+    }
+    if let Some(user_agent) = optional_env("FETCH_USER_AGENT") {
+        config.user_agent = user_agent;
+    }
+    if let Some(value) = optional_env("FETCH_ALLOW_PRIVATE_HOSTS") {
+        config.allow_private_hosts = parse_bool("FETCH_ALLOW_PRIVATE_HOSTS", &value)?;
+    }
+    Ok(config)
+}
+
+/// 读取重排序配置
+///
+/// `RERANK_PROVIDER` 选择提供方，默认 `siliconflow`。`RERANK_ENABLED` 未设置时，
+/// 显式设置了提供方或配置了密钥即启用重排序；托管服务缺少密钥、TEI 缺少端点时返回错误。
+/// 密钥优先读取 `RERANK_API_KEY`，SiliconFlow 兼容旧的 `SILICONFLOW_API_KEY`。
+fn rerank_config_from_env() -> Result<Option<RerankConfig>> {
+    let enabled = optional_env("RERANK_ENABLED")
+        .map(|value| parse_bool("RERANK_ENABLED", &value))
+        .transpose()?;
+    let explicit_provider = parse_env::<RerankProvider>("RERANK_PROVIDER")?;
+    let provider = explicit_provider.unwrap_or(RerankProvider::SiliconFlow);
+    let api_key = optional_env("RERANK_API_KEY").or_else(|| {
+        (provider == RerankProvider::SiliconFlow)
+            .then(|| optional_env("SILICONFLOW_API_KEY"))
+            .flatten()
+    });
+
+    match enabled {
+        Some(false) => return Ok(None),
+        None if explicit_provider.is_none() && api_key.is_none() => return Ok(None),
+        _ => {}
+    }
+    if provider.requires_api_key() && api_key.is_none() {
+        anyhow::bail!("RERANK_API_KEY (or SILICONFLOW_API_KEY) is required for {provider} reranker")
+    }
+
+    let endpoint = match optional_env("RERANK_ENDPOINT")
+        .or_else(|| provider.default_endpoint().map(str::to_string))
+    {
+        Some(endpoint) => endpoint,
+        None if provider.is_local() => String::new(),
+        None => anyhow::bail!("RERANK_ENDPOINT is required for {provider} reranker"),
+    };
+    let model =
+        optional_env("RERANK_MODEL").unwrap_or_else(|| provider.default_model().to_string());
+    let max_documents =
+        parse_env::<usize>("RERANK_MAX_DOCUMENTS")?.unwrap_or(provider.default_max_documents());
+    let max_document_tokens =
+        parse_env::<usize>("RERANK_MAX_DOCUMENT_TOKENS")?.unwrap_or(DEFAULT_MAX_DOCUMENT_TOKENS);
+    if max_documents == 0 || max_document_tokens == 0 {
+        anyhow::bail!("RERANK_MAX_DOCUMENTS and RERANK_MAX_DOCUMENT_TOKENS must be greater than 0")
+    }
+    let sigmoid_scores = optional_env("RERANK_SCORE_SIGMOID")
+        .map(|value| parse_bool("RERANK_SCORE_SIGMOID", &value))
+        .transpose()?
+        .unwrap_or(provider.returns_logits());
+
+    Ok(Some(RerankConfig {
+        provider,
+        api_key,
+        model,
+        endpoint,
+        http: http_config_from_env("RERANK", DEFAULT_TIMEOUT_SECS)?,
+        cache_capacity: parse_env::<usize>("RERANK_CACHE_CAPACITY")?
+            .unwrap_or(DEFAULT_RERANK_CACHE_CAPACITY),
+        max_documents,
+        max_document_tokens,
+        sigmoid_scores,
+    }))
+}
+
+/// 读取 LLM 配置，设置了 `LLM_BASE_URL` 时启用，此时必须同时设置 `LLM_MODEL`
+fn llm_config_from_env() -> Result<Option<LlmConfig>> {
+    let Some(base_url) = optional_env("LLM_BASE_URL") else {
+        return Ok(None);
+    };
+    let model =
+        optional_env("LLM_MODEL").context("LLM_MODEL is required when LLM_BASE_URL is set")?;
+
+    Ok(Some(LlmConfig {
+        base_url,
+        api_key: optional_env("LLM_API_KEY"),
+        model,
+        http: http_config_from_env("LLM", DEFAULT_LLM_TIMEOUT_SECS)?,
+        max_tokens: parse_env::<u32>("LLM_MAX_TOKENS")?,
+    }))
+}
+
+/// 读取某个上游的出站 HTTP 配置
+///
+/// 超时与重试次数按上游读取 `{prefix}_TIMEOUT_SECS`、`{prefix}_CONNECT_TIMEOUT_SECS`、
+/// `{prefix}_MAX_RETRIES`；熔断参数 `HTTP_BREAKER_THRESHOLD`、`HTTP_BREAKER_COOLDOWN_SECS`
+/// 对所有上游生效。
+fn http_config_from_env(prefix: &str, default_timeout_secs: u64) -> Result<HttpConfig> {
+    let request_timeout_secs =
+        parse_env::<u64>(&format!("{prefix}_TIMEOUT_SECS"))?.unwrap_or(default_timeout_secs);
+    let connect_timeout_secs = parse_env::<u64>(&format!("{prefix}_CONNECT_TIMEOUT_SECS"))?
+        .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS);
+    let max_retries =
+        parse_env::<u32>(&format!("{prefix}_MAX_RETRIES"))?.unwrap_or(DEFAULT_MAX_RETRIES);
+    let breaker_threshold =
+        parse_env::<u32>("HTTP_BREAKER_THRESHOLD")?.unwrap_or(DEFAULT_BREAKER_THRESHOLD);
+    let breaker_cooldown_secs =
+        parse_env::<u64>("HTTP_BREAKER_COOLDOWN_SECS")?.unwrap_or(DEFAULT_BREAKER_COOLDOWN_SECS);
+    if request_timeout_secs == 0 || connect_timeout_secs == 0 {
+        anyhow::bail!("{prefix} timeouts must be greater than 0")
+    }
+
+    Ok(HttpConfig {
+        connect_timeout: Duration::from_secs(connect_timeout_secs),
+        request_timeout: Duration::from_secs(request_timeout_secs),
+        max_retries,
+        breaker_threshold,
+        breaker_cooldown: Duration::from_secs(breaker_cooldown_secs),
+    })
+}
+
+fn optional_env(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_env<T>(key: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    optional_env(key)
+        .map(|value| {
+            value
+                .parse::<T>()
+                .map_err(|err| anyhow::anyhow!("{key} is invalid ({value}): {err}"))
         })
+        .transpose()
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => anyhow::bail!("{key} must be a boolean, got: {value}"),
     }
 }
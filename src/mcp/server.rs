@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use axum::{Router, middleware, routing::get};
+use axum::{Json, Router, middleware, routing::get};
 use rmcp::transport::streamable_http_server::{
     StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
 };
@@ -12,20 +12,132 @@ use super::{
     config::McpConfig,
     tools::SearxngTools,
 };
-use crate::searxng::client::SearxngClient;
+use crate::{
+    cratesio::{CratesIoClient, client::DEFAULT_USER_AGENT},
+    fetch::PageFetcher,
+    http::{CircuitState, CircuitStatus},
+    llm::LlmClient,
+    rerank::{Bm25Reranker, build_reranker},
+    searxng::{cache::SearchCache, client::SearxngClient, pool::UpstreamPool},
+};
+
+/// 健康检查：进程存活即返回 200；存在打开的熔断器或不健康的 searXNG 实例时 status 为 `degraded`
+async fn health_check(
+    client: SearxngClient,
+    crates_client: CratesIoClient,
+    llm: Option<LlmClient>,
+) -> Json<serde_json::Value> {
+    let mut circuits: Vec<CircuitStatus> = client.http().circuit_status();
+    if let Some(reranker) = client.reranker() {
+        circuits.extend(reranker.circuit_status());
+    }
+    circuits.extend(crates_client.http().circuit_status());
+    if let Some(llm) = &llm {
+        circuits.extend(llm.http().circuit_status());
+    }
+    let upstreams = client.pool().status();
 
-async fn health_check() -> &'static str {
-    "OK"
+    let degraded = circuits
+        .iter()
+        .any(|circuit| circuit.state != CircuitState::Closed)
+        || upstreams.iter().all(|upstream| !upstream.healthy);
+    Json(serde_json::json!({
+        "status": if degraded { "degraded" } else { "ok" },
+        "searxng_upstreams": upstreams,
+        "circuits": circuits,
+    }))
 }
 
 pub async fn serve(config: McpConfig) -> Result<()> {
     let auth_state = Arc::new(AuthState::new(config.auth_token));
-    let client = SearxngClient::new(config.searxng_url);
+    // 本地 BM25 本身不会失败，只为远程重排序配置回退
+    let rerank_fallback = config.rerank_fallback
+        && config
+            .rerank
+            .as_ref()
+            .is_some_and(|rerank_config| !rerank_config.provider.is_local());
+    let reranker = match config.rerank {
+        Some(rerank_config) => {
+            tracing::info!(
+                provider = %rerank_config.provider,
+                model = %rerank_config.model,
+                endpoint = %rerank_config.endpoint,
+                "Rerank enabled"
+            );
+            Some(build_reranker(rerank_config)?)
+        }
+        None => {
+            tracing::info!("Rerank disabled, results keep searXNG order");
+            None
+        }
+    };
+    tracing::info!(
+        upstreams = ?config.searxng_urls,
+        strategy = ?config.searxng_balance,
+        "searXNG upstreams configured"
+    );
+    let pool = Arc::new(UpstreamPool::new(
+        config.searxng_urls,
+        config.searxng_balance,
+    ));
+    let mut client = SearxngClient::with_pool(pool.clone(), reranker, config.searxng_http)?
+        .with_query_templates(Arc::new(config.rerank_templates));
+    if rerank_fallback {
+        tracing::info!("Lexical rerank fallback enabled");
+        client = client.with_rerank_fallback(Arc::new(Bm25Reranker::new()));
+    }
+    if let Some(cache_config) = config.search_cache {
+        tracing::info!(
+            capacity = cache_config.capacity,
+            path = ?cache_config.path,
+            "Search cache enabled"
+        );
+        client = client.with_cache(Arc::new(SearchCache::from_config(cache_config)?));
+    }
+    // 只有一个实例时无可切换的目标，不做主动探测
+    if let Some(interval) = config.searxng_health_check_interval
+        && pool.len() > 1
+    {
+        pool.spawn_health_checks(client.http().inner().clone(), interval);
+    }
+    let crates_client = CratesIoClient::with_config(
+        config
+            .crates_io_user_agent
+            .as_deref()
+            .unwrap_or(DEFAULT_USER_AGENT),
+        config.crates_io_http,
+    )?;
+    let fetcher = PageFetcher::from_config(config.fetch)?;
+    let llm = match config.llm {
+        Some(llm_config) => {
+            tracing::info!(
+                model = %llm_config.model,
+                base_url = %llm_config.base_url,
+                "Answer synthesis enabled"
+            );
+            Some(LlmClient::from_config(llm_config)?)
+        }
+        None => {
+            tracing::info!("LLM not configured, answer tool returns evidence only");
+            None
+        }
+    };
+    let search_concurrency = config.search_concurrency;
+    let (health_client, health_crates_client, health_llm) =
+        (client.clone(), crates_client.clone(), llm.clone());
     let ct = CancellationToken::new();
 
     let mcp_service: StreamableHttpService<SearxngTools, LocalSessionManager> =
         StreamableHttpService::new(
-            move || Ok(SearxngTools::new(client.clone())),
+            move || {
+                Ok(SearxngTools::new(
+                    client.clone(),
+                    crates_client.clone(),
+                    fetcher.clone(),
+                    llm.clone(),
+                    search_concurrency,
+                ))
+            },
             LocalSessionManager::default().into(),
             StreamableHttpServerConfig {
                 cancellation_token: ct.child_token(),
@@ -45,7 +157,10 @@ pub async fn serve(config: McpConfig) -> Result<()> {
     };
 
     let app = Router::new()
-        .route("/health", get(health_check))
+        .route(
+            "/health",
+            get(move || health_check(health_client, health_crates_client, health_llm)),
+        )
         .merge(mcp_router);
 
     let listener = tokio::net::TcpListener::bind(&config.bind).await?;
@@ -1,15 +1,30 @@
 use rmcp::{
-    ErrorData as McpError, ServerHandler,
+    ErrorData as McpError, Json, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{CallToolResult, Content, Implementation, ServerCapabilities, ServerInfo},
+    model::{Implementation, ServerCapabilities, ServerInfo},
     tool, tool_handler, tool_router,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::searxng::{
-    client::SearxngClient,
-    types::{OpenSearchResponse, QuerySearchResult},
+use crate::{
+    cratesio::{
+        CratesIoClient,
+        types::{CrateAdoptionResponse, CrateDetailResponse, CrateSort, CratesSearchResponse},
+    },
+    fetch::{PageFetcher, types::FetchUrlResponse},
+    llm::LlmClient,
+    research::{
+        Answerer, DeepSearchOptions, DeepSearcher,
+        types::{AnswerMode, AnswerResponse, DeepSearchResponse, PassageScoring},
+    },
+    search::{ImageSearchResponse, search_images},
+    searxng::{
+        client::SearxngClient,
+        types::{
+            OpenSearchResponse, RerankStatus, SafeSearch, SearchFilters, SearchOptions, TimeRange,
+        },
+    },
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -46,21 +61,192 @@ impl SearchType {
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OpenSearchParams {
+    /// 单个搜索关键词
+    #[serde(default)]
     pub query: String,
+    /// 多个搜索关键词，并发执行；可与 query 同时使用
+    #[serde(default)]
+    pub queries: Vec<String>,
     #[serde(default)]
     pub search_type: Option<SearchType>,
+    /// 返回结果条数，默认 20，最大 50
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// 起始页码（从 1 开始），可使用上一次返回的 next_page 继续翻页
+    #[serde(default, alias = "pageno")]
+    pub page: Option<usize>,
+    /// 跳过起始页的前若干条结果，继续翻页时传入上一次返回的 next_offset
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// 发布时间范围：day、week、month、year，不传则不限
+    #[serde(default)]
+    pub time_range: Option<TimeRange>,
+    /// 结果语言代码，如 `en`、`de`、`zh-CN`，`all` 表示不限
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 安全搜索级别：off、moderate、strict，不传使用 searXNG 默认值
+    #[serde(default)]
+    pub safesearch: Option<SafeSearch>,
+    /// 只使用指定的 searXNG 引擎，如 `["google", "wikipedia"]`，最多 20 个
+    #[serde(default)]
+    pub engines: Vec<String>,
+    /// 重排序后只保留得分最高的前 N 条（不超过 limit）；可配合较大的 limit 从更多候选中挑选
+    #[serde(default)]
+    pub top_n: Option<usize>,
+    /// 丢弃重排序相关性得分（relevance_score）低于该值的结果，仅在 rerank 为 applied 且重排序提供方不是 bm25 时生效
+    #[serde(default)]
+    pub min_score: Option<f64>,
+    /// 覆盖重排序 query 的指令，如 "Given a web search query, retrieve relevant passages"；
+    /// 可使用 `{query}`、`{category}`、`{date}` 占位符，不含 `{query}` 时原始搜索词追加在指令之后
+    #[serde(default)]
+    pub rerank_instruction: Option<String>,
+    /// 融合模式：按倒数排名融合合并所有 query 的结果，按规范化 URL 去重并记录每条结果命中的 query
+    #[serde(default)]
+    pub fuse: bool,
+    /// 融合模式下额外搜索的类别，与 search_type 一起参与融合
+    #[serde(default)]
+    pub search_types: Vec<SearchType>,
+    /// 多样性权衡系数（0~1）：按最大边际相关性重新排列结果，越小越偏向与已选结果不同的内容，1 表示只看相关性
+    #[serde(default)]
+    pub mmr_lambda: Option<f64>,
+    /// 同一域名最多返回的结果数，超出的结果被丢弃
+    #[serde(default)]
+    pub max_per_domain: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CratesSearchParams {
+    pub query: String,
+    /// 排序方式：downloads（默认）、recent-downloads、relevance、newly-added
+    #[serde(default)]
+    pub sort: Option<CrateSort>,
+    /// 页码（从 1 开始）
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// 每页条数，默认 10，最大 100
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateInfoParams {
+    /// crate 名称
+    pub name: String,
+    /// 版本要求（semver 语法，如 `^1.2`、`>=0.4, <0.6`），用于选择版本；不传则选择最新稳定版本
+    #[serde(default)]
+    pub version_req: Option<String>,
+    /// 最多返回的版本条数（从新到旧），默认 50
+    #[serde(default)]
+    pub max_versions: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrateAdoptionParams {
+    /// crate 名称
+    pub name: String,
+    /// 反向依赖页码（从 1 开始）
+    #[serde(default)]
+    pub page: Option<u32>,
+    /// 每页反向依赖条数，默认 20，最大 100
+    #[serde(default)]
+    pub per_page: Option<u32>,
+    /// 返回的每日下载序列天数，默认 30，最大 90（趋势摘要始终基于完整数据计算）
+    #[serde(default)]
+    pub days: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImageSearchParams {
+    /// 图片搜索关键词，多个关键词并发搜索，最多 10 个
+    pub keywords: Vec<String>,
+    /// 每个关键词返回的图片数量，默认 10，最大 50
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FetchUrlParams {
+    /// 要读取的网页地址（http/https）
+    pub url: String,
+    /// 本次返回的最大字符数，默认 8000，最大 40000
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+    /// 从正文第几个字符开始返回，用于读取上一次返回的 next_offset 之后的内容
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeepSearchParams {
+    pub query: String,
+    #[serde(default)]
+    pub search_type: Option<SearchType>,
+    /// 抓取并阅读的结果页面数，默认 3，最大 8
+    #[serde(default)]
+    pub pages: Option<usize>,
+    /// 每个页面返回的相关段落数，默认 3，最大 10
+    #[serde(default)]
+    pub passages_per_page: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerParams {
+    /// 需要回答的问题
+    pub question: String,
+    #[serde(default)]
+    pub search_type: Option<SearchType>,
+    /// 作为证据的搜索结果条数，默认 8，最大 20
+    #[serde(default)]
+    pub max_sources: Option<usize>,
 }
 
 #[derive(Clone)]
 pub struct SearxngTools {
     client: SearxngClient,
+    crates_client: CratesIoClient,
+    fetcher: PageFetcher,
+    deep_searcher: DeepSearcher,
+    answerer: Answerer,
+    search_concurrency: usize,
     tool_router: ToolRouter<Self>,
 }
 
 impl SearxngTools {
-    pub fn new(client: SearxngClient) -> Self {
+    pub const DEFAULT_LIMIT: usize = 20;
+    pub const MAX_LIMIT: usize = 50;
+    pub const MAX_QUERIES: usize = 10;
+    /// 融合模式下 query 与类别组合的最大搜索次数
+    pub const MAX_FUSED_SEARCHES: usize = 20;
+    pub const DEFAULT_IMAGE_LIMIT: usize = 10;
+    pub const DEFAULT_DEEP_PAGES: usize = 3;
+    pub const MAX_DEEP_PAGES: usize = 8;
+    pub const DEFAULT_PASSAGES_PER_PAGE: usize = 3;
+    pub const MAX_PASSAGES_PER_PAGE: usize = 10;
+    pub const DEFAULT_ANSWER_SOURCES: usize = 8;
+    pub const MAX_ANSWER_SOURCES: usize = 20;
+    pub const DEFAULT_FETCH_MAX_CHARS: usize = 8000;
+    pub const MAX_FETCH_MAX_CHARS: usize = 40000;
+    pub const DEFAULT_CRATES_PER_PAGE: u32 = 10;
+    pub const MAX_CRATES_PER_PAGE: u32 = 100;
+    pub const DEFAULT_MAX_VERSIONS: usize = 50;
+    pub const DEFAULT_REVERSE_DEPS_PER_PAGE: u32 = 20;
+    pub const DEFAULT_DOWNLOAD_DAYS: usize = 30;
+    pub const MAX_DOWNLOAD_DAYS: usize = 90;
+
+    pub fn new(
+        client: SearxngClient,
+        crates_client: CratesIoClient,
+        fetcher: PageFetcher,
+        llm: Option<LlmClient>,
+        search_concurrency: usize,
+    ) -> Self {
         Self {
+            deep_searcher: DeepSearcher::new(client.clone(), fetcher.clone()),
+            answerer: Answerer::new(client.clone(), llm),
             client,
+            crates_client,
+            fetcher,
+            search_concurrency: search_concurrency.max(1),
             tool_router: Self::tool_router(),
         }
     }
@@ -68,83 +254,486 @@ impl SearxngTools {
     async fn run_open_search(
         &self,
         params: Parameters<OpenSearchParams>,
-    ) -> Result<CallToolResult, McpError> {
+    ) -> Result<Json<OpenSearchResponse>, McpError> {
         let search_type = params.0.search_type.unwrap_or(SearchType::General);
         let search_type_str = search_type.as_str().to_string();
         let category = search_type.as_category();
-        let query = params.0.query.trim().to_string();
+        let filters = match SearchFilters::new(
+            params.0.time_range,
+            params.0.language.as_deref(),
+            params.0.safesearch,
+            &params.0.engines,
+        ) {
+            Ok(filters) => filters,
+            Err(err) => {
+                return Ok(Json(OpenSearchResponse {
+                    success: false,
+                    partial_success: false,
+                    search_type: search_type_str,
+                    results: Vec::new(),
+                    fused: None,
+                    error: Some(format!("{err:#}")),
+                }));
+            }
+        };
+        if let Some(min_score) = params.0.min_score
+            && !min_score.is_finite()
+        {
+            return Ok(Json(OpenSearchResponse {
+                success: false,
+                partial_success: false,
+                search_type: search_type_str,
+                results: Vec::new(),
+                fused: None,
+                error: Some(format!(
+                    "min_score must be a finite number, got: {min_score}"
+                )),
+            }));
+        }
+        if let Some(mmr_lambda) = params.0.mmr_lambda
+            && !(0.0..=1.0).contains(&mmr_lambda)
+        {
+            return Ok(Json(OpenSearchResponse {
+                success: false,
+                partial_success: false,
+                search_type: search_type_str,
+                results: Vec::new(),
+                fused: None,
+                error: Some(format!(
+                    "mmr_lambda must be between 0 and 1, got: {mmr_lambda}"
+                )),
+            }));
+        }
+        let options = SearchOptions {
+            limit: params
+                .0
+                .limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .clamp(1, Self::MAX_LIMIT),
+            page: params.0.page.unwrap_or(1).max(1),
+            offset: params.0.offset.unwrap_or(0),
+            filters,
+            top_n: params.0.top_n.map(|top_n| top_n.max(1)),
+            min_score: params.0.min_score,
+            rerank_instruction: params
+                .0
+                .rerank_instruction
+                .as_deref()
+                .map(str::trim)
+                .filter(|instruction| !instruction.is_empty())
+                .map(str::to_string),
+            mmr_lambda: params.0.mmr_lambda,
+            max_per_domain: params.0.max_per_domain.map(|max| max.max(1)),
+        };
 
-        if query.is_empty() {
-            return Ok(Self::response_to_result(OpenSearchResponse {
+        let mut queries: Vec<String> = Vec::new();
+        for query in std::iter::once(&params.0.query).chain(params.0.queries.iter()) {
+            let query = query.trim();
+            if !query.is_empty() && !queries.iter().any(|existing| existing == query) {
+                queries.push(query.to_string());
+            }
+        }
+
+        if queries.is_empty() {
+            return Ok(Json(OpenSearchResponse {
                 success: false,
+                partial_success: false,
                 search_type: search_type_str,
                 results: Vec::new(),
+                fused: None,
                 error: Some("query must not be empty".to_string()),
             }));
         }
-
-        let query_result = match self.client.search(&query, category).await {
-            Ok(response) => QuerySearchResult {
-                query: response.query,
-                success: response.success,
-                results: response.results,
-                error: response.error,
-            },
-            Err(err) => QuerySearchResult {
-                query,
+        if queries.len() > Self::MAX_QUERIES {
+            return Ok(Json(OpenSearchResponse {
                 success: false,
+                partial_success: false,
+                search_type: search_type_str,
                 results: Vec::new(),
-                error: Some(err.to_string()),
+                fused: None,
+                error: Some(format!(
+                    "too many queries: {} (max {})",
+                    queries.len(),
+                    Self::MAX_QUERIES
+                )),
+            }));
+        }
+
+        let mut categories = vec![category];
+        for extra in &params.0.search_types {
+            if !categories.contains(&extra.as_category()) {
+                categories.push(extra.as_category());
             }
+        }
+        if !params.0.fuse && categories.len() > 1 {
+            return Ok(Json(OpenSearchResponse {
+                success: false,
+                partial_success: false,
+                search_type: search_type_str,
+                results: Vec::new(),
+                fused: None,
+                error: Some("search_types requires fuse=true".to_string()),
+            }));
+        }
+        if queries.len() * categories.len() > Self::MAX_FUSED_SEARCHES {
+            return Ok(Json(OpenSearchResponse {
+                success: false,
+                partial_success: false,
+                search_type: search_type_str,
+                results: Vec::new(),
+                fused: None,
+                error: Some(format!(
+                    "too many searches: {} queries x {} search types (max {})",
+                    queries.len(),
+                    categories.len(),
+                    Self::MAX_FUSED_SEARCHES
+                )),
+            }));
+        }
+
+        let (results, fused) = if params.0.fuse {
+            let (results, fused) = self
+                .client
+                .search_fused(&queries, &categories, &options, self.search_concurrency)
+                .await;
+            (results, Some(fused))
+        } else {
+            let results = self
+                .client
+                .search_many(&queries, category, &options, self.search_concurrency)
+                .await;
+            (results, None)
         };
 
-        Ok(Self::response_to_result(OpenSearchResponse {
-            success: query_result.success,
+        let failed = results.iter().filter(|item| !item.success).count();
+        let error = if failed == 0 {
+            None
+        } else {
+            Some(format!("{} of {} queries failed", failed, results.len()))
+        };
+
+        Ok(Json(OpenSearchResponse {
+            success: failed == 0,
+            partial_success: failed > 0 && failed < results.len(),
             search_type: search_type_str,
-            results: vec![query_result],
-            error: None,
+            results,
+            fused,
+            error,
         }))
     }
 
-    fn response_to_result(response: OpenSearchResponse) -> CallToolResult {
-        match serde_json::to_value(&response) {
-            Ok(value) => CallToolResult::structured(value),
-            Err(err) => {
-                let fallback_results = response
-                    .results
-                    .into_iter()
-                    .map(|item| {
-                        serde_json::json!({
-                            "query": item.query,
-                            "success": item.success,
-                            "results": item
-                                .results
-                                .into_iter()
-                                .map(|result| {
-                                    serde_json::json!({
-                                        "url": result.url,
-                                        "description": result.description,
-                                    })
-                                })
-                                .collect::<Vec<_>>(),
-                            "error": item.error,
-                        })
-                    })
-                    .collect::<Vec<_>>();
-                let fallback = serde_json::json!({
-                    "success": response.success,
-                    "search_type": response.search_type,
-                    "results": fallback_results,
-                    "error": response
-                        .error
-                        .or_else(|| Some(format!("structured serialization failed: {}", err))),
-                });
-                let fallback_text = serde_json::to_string(&fallback).unwrap_or_else(|_| {
-                    "{\"success\":false,\"error\":\"fallback serialization failed\"}".to_string()
-                });
-                CallToolResult::success(vec![Content::text(fallback_text)])
+    async fn run_image_search(
+        &self,
+        params: Parameters<ImageSearchParams>,
+    ) -> Result<Json<ImageSearchResponse>, McpError> {
+        let limit = params
+            .0
+            .limit
+            .unwrap_or(Self::DEFAULT_IMAGE_LIMIT)
+            .clamp(1, Self::MAX_LIMIT);
+
+        if params.0.keywords.len() > Self::MAX_QUERIES {
+            return Ok(Json(ImageSearchResponse {
+                success: false,
+                results: Vec::new(),
+                error: Some(format!(
+                    "too many keywords: {} (max {})",
+                    params.0.keywords.len(),
+                    Self::MAX_QUERIES
+                )),
+            }));
+        }
+
+        let response = search_images(&self.client, &params.0.keywords, limit).await;
+        Ok(Json(response))
+    }
+
+    async fn run_deep_search(
+        &self,
+        params: Parameters<DeepSearchParams>,
+    ) -> Result<Json<DeepSearchResponse>, McpError> {
+        let search_type = params.0.search_type.unwrap_or(SearchType::General);
+        let query = params.0.query.trim().to_string();
+        let options = DeepSearchOptions {
+            pages: params
+                .0
+                .pages
+                .unwrap_or(Self::DEFAULT_DEEP_PAGES)
+                .clamp(1, Self::MAX_DEEP_PAGES),
+            passages_per_page: params
+                .0
+                .passages_per_page
+                .unwrap_or(Self::DEFAULT_PASSAGES_PER_PAGE)
+                .clamp(1, Self::MAX_PASSAGES_PER_PAGE),
+        };
+        let failed = |error: String| DeepSearchResponse {
+            success: false,
+            query: query.clone(),
+            search_type: search_type.as_str().to_string(),
+            rerank: RerankStatus::Skipped,
+            passage_scoring: PassageScoring::Lexical,
+            pages: Vec::new(),
+            error: Some(error),
+        };
+
+        if query.is_empty() {
+            return Ok(Json(failed("query must not be empty".to_string())));
+        }
+
+        let response = match self
+            .deep_searcher
+            .search(&query, search_type.as_category(), &options)
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => failed(format!("{err:#}")),
+        };
+        Ok(Json(response))
+    }
+
+    async fn run_answer(
+        &self,
+        params: Parameters<AnswerParams>,
+    ) -> Result<Json<AnswerResponse>, McpError> {
+        let search_type = params.0.search_type.unwrap_or(SearchType::General);
+        let question = params.0.question.trim().to_string();
+        let max_sources = params
+            .0
+            .max_sources
+            .unwrap_or(Self::DEFAULT_ANSWER_SOURCES)
+            .clamp(1, Self::MAX_ANSWER_SOURCES);
+        let failed = |error: String| AnswerResponse {
+            success: false,
+            question: question.clone(),
+            mode: AnswerMode::EvidenceOnly,
+            answer: None,
+            model: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
+            rerank: RerankStatus::Skipped,
+            error: Some(error),
+        };
+
+        if question.is_empty() {
+            return Ok(Json(failed("question must not be empty".to_string())));
+        }
+
+        let response = match self
+            .answerer
+            .answer(&question, search_type.as_category(), max_sources)
+            .await
+        {
+            Ok(response) => response,
+            Err(err) => failed(format!("{err:#}")),
+        };
+        Ok(Json(response))
+    }
+
+    async fn run_fetch_url(
+        &self,
+        params: Parameters<FetchUrlParams>,
+    ) -> Result<Json<FetchUrlResponse>, McpError> {
+        let url = params.0.url.trim().to_string();
+        let offset = params.0.offset.unwrap_or(0);
+        let max_chars = params
+            .0
+            .max_chars
+            .unwrap_or(Self::DEFAULT_FETCH_MAX_CHARS)
+            .clamp(1, Self::MAX_FETCH_MAX_CHARS);
+        let mut response = FetchUrlResponse {
+            success: false,
+            url: url.clone(),
+            final_url: None,
+            canonical_url: None,
+            title: None,
+            byline: None,
+            published_at: None,
+            content: String::new(),
+            offset,
+            total_chars: 0,
+            next_offset: None,
+            error: None,
+        };
+
+        if url.is_empty() {
+            response.error = Some("url must not be empty".to_string());
+            return Ok(Json(response));
+        }
+
+        match self.fetcher.fetch(&url).await {
+            Ok(page) => {
+                let (content, next_offset) = page.window(offset, max_chars);
+                response.success = true;
+                response.total_chars = page.markdown.chars().count();
+                response.content = content;
+                response.next_offset = next_offset;
+                response.final_url = Some(page.final_url);
+                response.canonical_url = Some(page.canonical_url);
+                response.title = page.title;
+                response.byline = page.byline;
+                response.published_at = page.published_at;
+            }
+            Err(err) => response.error = Some(format!("{err:#}")),
+        }
+
+        Ok(Json(response))
+    }
+
+    async fn run_crates_search(
+        &self,
+        params: Parameters<CratesSearchParams>,
+    ) -> Result<Json<CratesSearchResponse>, McpError> {
+        let query = params.0.query.trim().to_string();
+        let sort = params.0.sort.unwrap_or_default();
+        let page = params.0.page.unwrap_or(1).max(1);
+        let per_page = params
+            .0
+            .per_page
+            .unwrap_or(Self::DEFAULT_CRATES_PER_PAGE)
+            .clamp(1, Self::MAX_CRATES_PER_PAGE);
+        let mut response = CratesSearchResponse {
+            success: false,
+            query: query.clone(),
+            sort: sort.as_str().to_string(),
+            page,
+            per_page,
+            total: 0,
+            crates: Vec::new(),
+            error: None,
+        };
+
+        if query.is_empty() {
+            response.error = Some("query must not be empty".to_string());
+            return Ok(Json(response));
+        }
+
+        match self
+            .crates_client
+            .search(&query, sort, page, per_page)
+            .await
+        {
+            Ok(result) => {
+                response.success = true;
+                response.total = result.total;
+                response.crates = result.crates;
+            }
+            Err(err) => response.error = Some(format!("{err:#}")),
+        }
+
+        Ok(Json(response))
+    }
+
+    async fn run_crate_info(
+        &self,
+        params: Parameters<CrateInfoParams>,
+    ) -> Result<Json<CrateDetailResponse>, McpError> {
+        let name = params.0.name.trim().to_string();
+        let version_req = params
+            .0
+            .version_req
+            .map(|req| req.trim().to_string())
+            .filter(|req| !req.is_empty());
+        let max_versions = params
+            .0
+            .max_versions
+            .unwrap_or(Self::DEFAULT_MAX_VERSIONS)
+            .max(1);
+        let mut response = CrateDetailResponse {
+            success: false,
+            name: name.clone(),
+            version_req: version_req.clone(),
+            crate_info: None,
+            selected_version: None,
+            versions_total: 0,
+            versions: Vec::new(),
+            error: None,
+        };
+
+        if name.is_empty() {
+            response.error = Some("name must not be empty".to_string());
+            return Ok(Json(response));
+        }
+
+        match self
+            .crates_client
+            .crate_detail(&name, version_req.as_deref())
+            .await
+        {
+            Ok(detail) => {
+                response.success = true;
+                response.versions_total = detail.versions.len();
+                response.versions = detail.versions.into_iter().take(max_versions).collect();
+                response.crate_info = Some(detail.info);
+                response.selected_version = Some(detail.selected);
+            }
+            Err(err) => response.error = Some(format!("{err:#}")),
+        }
+
+        Ok(Json(response))
+    }
+
+    async fn run_crate_adoption(
+        &self,
+        params: Parameters<CrateAdoptionParams>,
+    ) -> Result<Json<CrateAdoptionResponse>, McpError> {
+        let name = params.0.name.trim().to_string();
+        let page = params.0.page.unwrap_or(1).max(1);
+        let per_page = params
+            .0
+            .per_page
+            .unwrap_or(Self::DEFAULT_REVERSE_DEPS_PER_PAGE)
+            .clamp(1, Self::MAX_CRATES_PER_PAGE);
+        let days = params
+            .0
+            .days
+            .unwrap_or(Self::DEFAULT_DOWNLOAD_DAYS)
+            .clamp(1, Self::MAX_DOWNLOAD_DAYS);
+        let mut response = CrateAdoptionResponse {
+            success: false,
+            name: name.clone(),
+            page,
+            per_page,
+            reverse_dependencies_total: 0,
+            reverse_dependencies: Vec::new(),
+            downloads: None,
+            error: None,
+        };
+
+        if name.is_empty() {
+            response.error = Some("name must not be empty".to_string());
+            return Ok(Json(response));
+        }
+
+        let (reverse_dependencies, downloads) = tokio::join!(
+            self.crates_client
+                .reverse_dependencies(&name, page, per_page),
+            self.crates_client.download_stats(&name)
+        );
+
+        let mut errors = Vec::new();
+        match reverse_dependencies {
+            Ok(result) => {
+                response.reverse_dependencies_total = result.total;
+                response.reverse_dependencies = result.dependencies;
+            }
+            Err(err) => errors.push(format!("reverse dependencies: {err:#}")),
+        }
+        match downloads {
+            Ok(mut result) => {
+                for series in &mut result.series {
+                    let skip = series.daily.len().saturating_sub(days);
+                    series.daily.drain(..skip);
+                }
+                response.downloads = Some(result);
             }
+            Err(err) => errors.push(format!("downloads: {err:#}")),
         }
+
+        response.success = errors.is_empty();
+        if !errors.is_empty() {
+            response.error = Some(errors.join("; "));
+        }
+        Ok(Json(response))
     }
 }
 
@@ -156,7 +745,7 @@ impl ServerHandler for SearxngTools {
                 .enable_tools()
                 .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some("搜索服务，提供 opensearch 工具；opensearch 支持按 search_type 选择类别并对单个 query 进行查询".to_string()),
+            instructions: Some("搜索服务，提供 opensearch、deep_search、answer、image_search、fetch_url、crates_search、crate_info 与 crate_adoption 工具；opensearch 支持按 search_type 选择类别，并对单个 query 或多个 queries 并发查询；deep_search 会抓取排名靠前的页面并返回相关段落；answer 基于搜索结果生成带编号引用的答案（未配置 LLM 时只返回证据）；image_search 用于多关键词图片搜索；fetch_url 用于读取搜索结果网页的正文；crates_search 用于检索 crates.io 上的 Rust crate，crate_info 用于查看 crate 的版本、features 与依赖，crate_adoption 用于查看反向依赖与下载趋势".to_string()),
             ..Default::default()
         }
     }
@@ -166,12 +755,193 @@ impl ServerHandler for SearxngTools {
 impl SearxngTools {
     #[tool(
         name = "opensearch",
-        description = "搜索工具：search type 支持 general（通用搜索）；news（新闻搜索）；images（图示搜索）；videos（视频搜索）；science（学术搜索）。一次请求只接受一个 query 关键词，在消息中标注消息来源"
+        description = "搜索工具：search type 支持 general（通用搜索）；news（新闻搜索）；images（图示搜索）；videos（视频搜索）；science（学术搜索）。使用 query 传入单个关键词，或使用 queries 传入多个关键词（最多 10 个）并发搜索，每个关键词单独返回成功/失败，部分失败时 partial_success 为 true；在消息中标注消息来源。limit 控制返回条数（默认 20，最大 50），page 指定起始页，继续翻页时把返回的 next_page 与 next_offset 分别作为 page 与 offset 传入。可选 time_range（day/week/month/year）限定发布时间、language（如 de、zh-CN）限定语言、safesearch（off/moderate/strict）、engines 指定 searXNG 引擎列表，例如查找上周的德语新闻时使用 search_type=news、time_range=week、language=de。返回中的 answers（直接答案）与 infoboxes（知识卡片）可直接引用，suggestions 与 corrections 可作为新的 query 重试。warnings 列出无响应的引擎（如被 CAPTCHA 限流），结果过少时会自动排除这些引擎重试一次，excluded_engines 记录被排除的引擎。经过重排序的结果带有 relevance_score，可用 top_n 只保留最相关的前 N 条、用 min_score 丢弃得分过低的结果，rerank_instruction 可为指令微调的重排序模型指定本次的任务指令。多个相关 query 结果重叠较多时可设置 fuse=true，按倒数排名融合为一个去重后的 fused 列表（每条带 fusion_score、matched_queries 与 matched_categories），并可用 search_types 同时融合多个类别。结果集中在少数网站时，可用 mmr_lambda（如 0.7）按内容多样性重新排列、用 max_per_domain 限制同一域名的条数。rerank 字段表示结果是否经过重排序（applied）、改用本地词法排序（lexical）或保持原始顺序（fallback/disabled/skipped）"
     )]
     async fn opensearch(
         &self,
         params: Parameters<OpenSearchParams>,
-    ) -> Result<CallToolResult, McpError> {
+    ) -> Result<Json<OpenSearchResponse>, McpError> {
         self.run_open_search(params).await
     }
+
+    #[tool(
+        name = "image_search",
+        description = "图片搜索工具：传入一个或多个关键词（keywords，最多 10 个）并发搜索图片，每张图片返回原图地址 image_url、描述、缩略图 thumbnail_url、来源页面 source_url、分辨率 resolution 与格式 format（searXNG 提供时）。limit 为每个关键词的图片数量，默认 10，最大 50"
+    )]
+    async fn image_search(
+        &self,
+        params: Parameters<ImageSearchParams>,
+    ) -> Result<Json<ImageSearchResponse>, McpError> {
+        self.run_image_search(params).await
+    }
+
+    #[tool(
+        name = "deep_search",
+        description = "深度搜索工具：先搜索并重排序，再并行抓取排名靠前的 pages 个结果页面（默认 3，最大 8），将正文切分为段落并按与 query 的相关性重新打分，每个页面返回最相关的 passages_per_page 个段落（默认 3）及来源 url。适合需要阅读原文而非摘要的问题，可省去多次 opensearch + fetch_url 往返；回答时请标注段落来源"
+    )]
+    async fn deep_search(
+        &self,
+        params: Parameters<DeepSearchParams>,
+    ) -> Result<Json<DeepSearchResponse>, McpError> {
+        self.run_deep_search(params).await
+    }
+
+    #[tool(
+        name = "answer",
+        description = "问答工具：针对 question 搜索并重排序，取前 max_sources 个结果（默认 8，最大 20）作为编号来源，由服务端配置的 LLM 生成带 [n] 引用标记的答案，citations 列出实际引用的来源编号，sources 给出编号对应的 url 与摘要。mode 为 synthesized 表示已生成答案；为 evidence_only（未配置 LLM）或 fallback（LLM 调用失败）时只返回 sources，需要自行根据来源作答"
+    )]
+    async fn answer(
+        &self,
+        params: Parameters<AnswerParams>,
+    ) -> Result<Json<AnswerResponse>, McpError> {
+        self.run_answer(params).await
+    }
+
+    #[tool(
+        name = "fetch_url",
+        description = "网页阅读工具：下载 url 指向的网页，去除导航、评论等无关内容后提取正文，转换为保留链接的 Markdown，并返回标题 title、作者 byline、发布时间 published_at 与规范地址 canonical_url。正文按 max_chars 截断（默认 8000），返回的 next_offset 不为空时可作为 offset 继续读取后续内容"
+    )]
+    async fn fetch_url(
+        &self,
+        params: Parameters<FetchUrlParams>,
+    ) -> Result<Json<FetchUrlResponse>, McpError> {
+        self.run_fetch_url(params).await
+    }
+
+    #[tool(
+        name = "crates_search",
+        description = "crates.io 搜索工具：按关键词搜索 Rust crate，返回名称、最新稳定版本、描述、下载量与仓库/文档链接。sort 支持 downloads（总下载量，默认）、recent-downloads（近期下载量）、relevance（相关度）、newly-added（最新发布）；page/per_page 控制分页（per_page 最大 100），返回 total 为匹配总数"
+    )]
+    async fn crates_search(
+        &self,
+        params: Parameters<CratesSearchParams>,
+    ) -> Result<Json<CratesSearchResponse>, McpError> {
+        self.run_crates_search(params).await
+    }
+
+    #[tool(
+        name = "crate_info",
+        description = "crate 详情工具：输入 crate 名称与可选的 semver 版本要求（version_req），返回所有版本（含 yanked 标记、MSRV rust_version、license、发布时间），以及满足要求的最高未 yank 版本的 cargo features 与 normal/dev/build 依赖及其版本要求。用于选择兼容版本，避免臆造版本号"
+    )]
+    async fn crate_info(
+        &self,
+        params: Parameters<CrateInfoParams>,
+    ) -> Result<Json<CrateDetailResponse>, McpError> {
+        self.run_crate_info(params).await
+    }
+
+    #[tool(
+        name = "crate_adoption",
+        description = "crate 采用度工具：返回依赖该 crate 的下游 crate（分页，按下游下载量排序，含版本要求与依赖类型），以及近 90 天按版本拆分的每日下载序列和摘要（近 30 天下载量、相对之前 30 天的趋势百分比、最新版本下载占比）。用于判断 crate 是否仍在维护、是否被广泛采用"
+    )]
+    async fn crate_adoption(
+        &self,
+        params: Parameters<CrateAdoptionParams>,
+    ) -> Result<Json<CrateAdoptionResponse>, McpError> {
+        self.run_crate_adoption(params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use axum::{Json as AxumJson, Router, extract::Query, routing::get};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{fetch::FetchConfig, rerank::Bm25Reranker};
+
+    /// 按 query 返回固定结果的 searXNG，两个 query 共享 `https://shared.example`
+    async fn spawn_searxng() -> String {
+        async fn search(Query(params): Query<HashMap<String, String>>) -> AxumJson<Value> {
+            let query = &params["q"];
+            let results = if params["pageno"] == "1" {
+                json!([
+                    { "url": "https://shared.example/", "title": "shared rust tokio", "content": "" },
+                    { "url": format!("https://{query}.example/"), "title": query, "content": "" },
+                ])
+            } else {
+                json!([])
+            };
+            AxumJson(json!({ "results": results }))
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, Router::new().route("/search", get(search)))
+                .await
+                .unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    async fn tools() -> SearxngTools {
+        let client =
+            SearxngClient::new_with_rerank(spawn_searxng().await, Arc::new(Bm25Reranker::new()));
+        SearxngTools::new(
+            client,
+            CratesIoClient::new(),
+            PageFetcher::from_config(FetchConfig::default()).unwrap(),
+            None,
+            4,
+        )
+    }
+
+    #[test]
+    fn every_tool_declares_output_schema() {
+        let tools = SearxngTools::tool_router().list_all();
+        assert_eq!(tools.len(), 8);
+        for tool in tools {
+            let schema = tool
+                .output_schema
+                .unwrap_or_else(|| panic!("{} has no output schema", tool.name));
+            assert_eq!(schema["type"], "object", "{}", tool.name);
+            assert!(
+                schema["properties"].get("success").is_some(),
+                "{}",
+                tool.name
+            );
+        }
+    }
+
+    #[test]
+    fn opensearch_schema_declares_fused_and_scores() {
+        let tool = SearxngTools::tool_router()
+            .get("opensearch")
+            .cloned()
+            .unwrap();
+        let schema = serde_json::to_string(&tool.output_schema.unwrap()).unwrap();
+        for field in [
+            "fused",
+            "fusion_score",
+            "matched_queries",
+            "relevance_score",
+            "next_offset",
+        ] {
+            assert!(schema.contains(&format!("\"{field}\"")), "{field}");
+        }
+    }
+
+    #[tokio::test]
+    async fn fused_search_returns_scores() {
+        let params: OpenSearchParams =
+            serde_json::from_value(json!({ "queries": ["rust", "tokio"], "fuse": true })).unwrap();
+        let Json(response) = tools()
+            .await
+            .run_open_search(Parameters(params))
+            .await
+            .unwrap();
+        let value = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(value["success"], true);
+        let fused = value["fused"].as_array().unwrap();
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0]["url"], "https://shared.example/");
+        assert_eq!(fused[0]["matched_queries"], json!(["rust", "tokio"]));
+        for entry in fused {
+            assert!(entry["fusion_score"].as_f64().unwrap() > 0.0);
+            assert!(entry["relevance_score"].is_number());
+        }
+    }
 }
@@ -0,0 +1,274 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt, stream};
+use tracing::debug;
+
+use super::{
+    bm25::is_cjk,
+    reranker::{Reranker, finish_results},
+    types::{RerankOptions, RerankResult},
+};
+use crate::http::CircuitStatus;
+
+pub const DEFAULT_MAX_DOCUMENTS: usize = 64;
+/// TEI 默认的 `--max-client-batch-size`
+pub const DEFAULT_TEI_MAX_DOCUMENTS: usize = 32;
+pub const DEFAULT_MAX_DOCUMENT_TOKENS: usize = 1024;
+/// 同时进行的批次请求数
+const MAX_CONCURRENT_BATCHES: usize = 4;
+
+/// 分批重排序包装
+///
+/// 文档按估算的 token 数截断后，按 `max_documents` 切分为多个批次并发请求，再合并得分。
+/// `sigmoid` 为提供方的固定属性：返回原始 logits 的提供方总是经 sigmoid 映射到 0~1，
+/// 其余提供方的得分保持原样，保证不同调用与批次之间的得分尺度一致。
+pub struct BatchedReranker {
+    inner: Arc<dyn Reranker>,
+    max_documents: usize,
+    max_document_tokens: usize,
+    sigmoid: bool,
+}
+
+impl BatchedReranker {
+    pub fn new(
+        inner: Arc<dyn Reranker>,
+        max_documents: usize,
+        max_document_tokens: usize,
+        sigmoid: bool,
+    ) -> Self {
+        Self {
+            inner,
+            max_documents: max_documents.max(1),
+            max_document_tokens: max_document_tokens.max(1),
+            sigmoid,
+        }
+    }
+}
+
+#[async_trait]
+impl Reranker for BatchedReranker {
+    fn provider(&self) -> &'static str {
+        self.inner.provider()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn uses_query_template(&self) -> bool {
+        self.inner.uses_query_template()
+    }
+
+    fn absolute_scores(&self) -> bool {
+        self.inner.absolute_scores()
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
+        let truncated: Vec<String> = documents
+            .iter()
+            .map(|document| truncate_to_tokens(document, self.max_document_tokens))
+            .collect();
+        let batches: Vec<(usize, Vec<String>)> = truncated
+            .chunks(self.max_documents)
+            .enumerate()
+            .map(|(batch, chunk)| (batch * self.max_documents, chunk.to_vec()))
+            .collect();
+        debug!(
+            documents_count = documents.len(),
+            batches = batches.len(),
+            "Reranking documents in batches"
+        );
+
+        // 全局前 N 条必然包含在各批次的前 N 条之中，因此可以把 top_n 转发给每个批次
+        let mut results: Vec<RerankResult> = stream::iter(batches)
+            .map(|(offset, batch)| async move {
+                let results = self.inner.rerank(query, batch, options).await?;
+                Ok::<_, anyhow::Error>(
+                    results
+                        .into_iter()
+                        .map(|result| RerankResult {
+                            index: result.index + offset,
+                            ..result
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .buffer_unordered(MAX_CONCURRENT_BATCHES)
+            .try_concat()
+            .await?;
+
+        if self.sigmoid {
+            for result in &mut results {
+                result.relevance_score = sigmoid(result.relevance_score);
+            }
+        }
+        if options.return_documents {
+            // 返回未截断的原文
+            for result in &mut results {
+                result.document = documents.get(result.index).cloned();
+            }
+        }
+        finish_results(&mut results, options);
+        Ok(results)
+    }
+
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.inner.circuit_status()
+    }
+}
+
+/// 估算文本的 token 数：中日韩字符按 1 个 token，其余字符按 4 个字符 1 个 token
+pub fn estimate_tokens(text: &str) -> usize {
+    let (cjk, other) = text.chars().fold((0usize, 0usize), |(cjk, other), ch| {
+        if is_cjk(ch) {
+            (cjk + 1, other)
+        } else {
+            (cjk, other + 1)
+        }
+    });
+    cjk + other.div_ceil(4)
+}
+
+/// 按估算的 token 数截断文本，未超出预算时原样返回
+fn truncate_to_tokens(text: &str, budget: usize) -> String {
+    if estimate_tokens(text) <= budget {
+        return text.to_string();
+    }
+    // 以四分之一 token 为单位累计，避免浮点误差
+    let mut quarters = 0;
+    let limit = budget * 4;
+    let end = text
+        .char_indices()
+        .find(|(_, ch)| {
+            quarters += if is_cjk(*ch) { 4 } else { 1 };
+            quarters > limit
+        })
+        .map_or(text.len(), |(index, _)| index);
+    text[..end].to_string()
+}
+
+fn sigmoid(score: f64) -> f64 {
+    1.0 / (1.0 + (-score).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// 按文档长度打分，并记录每个批次的大小
+    #[derive(Default)]
+    struct LengthReranker {
+        batches: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl Reranker for LengthReranker {
+        fn provider(&self) -> &'static str {
+            "test"
+        }
+
+        fn model(&self) -> &str {
+            "length"
+        }
+
+        async fn rerank(
+            &self,
+            _query: &str,
+            documents: Vec<String>,
+            options: &RerankOptions,
+        ) -> Result<Vec<RerankResult>> {
+            self.batches.lock().unwrap().push(documents.len());
+            let mut results: Vec<RerankResult> = documents
+                .iter()
+                .enumerate()
+                .map(|(index, document)| RerankResult {
+                    index,
+                    relevance_score: document.len() as f64 - 2.0,
+                    document: None,
+                })
+                .collect();
+            finish_results(&mut results, options);
+            Ok(results)
+        }
+    }
+
+    fn documents(lengths: &[usize]) -> Vec<String> {
+        lengths.iter().map(|&length| "a".repeat(length)).collect()
+    }
+
+    #[tokio::test]
+    async fn merges_batches_with_global_indices() {
+        let inner = Arc::new(LengthReranker::default());
+        let reranker = BatchedReranker::new(inner.clone(), 2, 1024, false);
+        let results = reranker
+            .rerank("q", documents(&[1, 5, 3, 4, 2]), &RerankOptions::default())
+            .await
+            .unwrap();
+
+        let mut batches = inner.batches.lock().unwrap().clone();
+        batches.sort();
+        assert_eq!(batches, vec![1, 2, 2]);
+        let order: Vec<usize> = results.iter().map(|result| result.index).collect();
+        assert_eq!(order, vec![1, 3, 2, 4, 0]);
+        // 未启用 sigmoid 时保持提供方的原始得分
+        assert_eq!(results[0].relevance_score, 3.0);
+        assert_eq!(results[4].relevance_score, -1.0);
+    }
+
+    #[tokio::test]
+    async fn sigmoid_is_applied_regardless_of_score_range() {
+        let reranker = BatchedReranker::new(Arc::new(LengthReranker::default()), 64, 1024, true);
+        // 所有原始得分都在 0~1 之内时也要映射，保证与其他调用的尺度一致
+        let results = reranker
+            .rerank("q", documents(&[2, 3]), &RerankOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(results[0].relevance_score, sigmoid(1.0));
+        assert_eq!(results[1].relevance_score, sigmoid(0.0));
+    }
+
+    #[tokio::test]
+    async fn returns_original_documents_and_top_n() {
+        let reranker = BatchedReranker::new(Arc::new(LengthReranker::default()), 2, 2, false);
+        let options = RerankOptions {
+            top_n: Some(2),
+            return_documents: true,
+        };
+        let results = reranker
+            .rerank("q", documents(&[1, 20, 12]), &options)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        // 截断后两者的估算得分相同，按原始顺序排列，并返回未截断的原文
+        assert_eq!(results[0].index, 1);
+        assert_eq!(
+            results[0].document.as_deref(),
+            Some("a".repeat(20).as_str())
+        );
+    }
+
+    #[test]
+    fn estimates_cjk_and_latin_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens("中文ab"), 3);
+    }
+
+    #[test]
+    fn truncates_to_token_budget() {
+        assert_eq!(truncate_to_tokens("abcdefgh", 2), "abcdefgh");
+        assert_eq!(truncate_to_tokens("abcdefghij", 2), "abcdefgh");
+        assert_eq!(truncate_to_tokens("中文分词测试", 3), "中文分");
+        assert_eq!(truncate_to_tokens("ab中文", 1), "ab");
+    }
+}
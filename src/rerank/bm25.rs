@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{
+    reranker::{Reranker, fill_documents, finish_results},
+    types::{RerankOptions, RerankResult},
+};
+
+/// 词频饱和参数
+const K1: f64 = 1.2;
+/// 文档长度归一化参数
+const B: f64 = 0.75;
+
+/// 进程内 BM25 重排序，不需要 API 密钥
+///
+/// 得分按本次请求的最高分归一化到 0~1，没有任何查询词命中的文档得分为 0。
+#[derive(Debug, Clone, Default)]
+pub struct Bm25Reranker;
+
+impl Bm25Reranker {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Reranker for Bm25Reranker {
+    fn provider(&self) -> &'static str {
+        "bm25"
+    }
+
+    fn model(&self) -> &str {
+        "bm25"
+    }
+
+    fn uses_query_template(&self) -> bool {
+        false
+    }
+
+    fn absolute_scores(&self) -> bool {
+        false
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
+        let scores = bm25_scores(query, &documents);
+        let max = scores.iter().copied().fold(0.0, f64::max);
+        let mut results: Vec<RerankResult> = scores
+            .into_iter()
+            .enumerate()
+            .map(|(index, score)| RerankResult {
+                index,
+                relevance_score: if max > 0.0 { score / max } else { 0.0 },
+                document: None,
+            })
+            .collect();
+        finish_results(&mut results, options);
+        if options.return_documents {
+            fill_documents(&mut results, &documents);
+        }
+        Ok(results)
+    }
+}
+
+/// 以 `documents` 为语料计算每个文档对 `query` 的 BM25 得分（未归一化）
+pub fn bm25_scores(query: &str, documents: &[String]) -> Vec<f64> {
+    let query_terms: HashSet<String> = tokenize(query).into_iter().collect();
+    if query_terms.is_empty() || documents.is_empty() {
+        return vec![0.0; documents.len()];
+    }
+
+    let term_counts: Vec<(HashMap<String, usize>, usize)> = documents
+        .iter()
+        .map(|document| {
+            let tokens = tokenize(document);
+            let len = tokens.len();
+            let mut counts = HashMap::new();
+            for token in tokens {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+            (counts, len)
+        })
+        .collect();
+
+    let doc_count = documents.len() as f64;
+    let avg_len = term_counts.iter().map(|(_, len)| *len as f64).sum::<f64>() / doc_count;
+    let idf: HashMap<&str, f64> = query_terms
+        .iter()
+        .map(|term| {
+            let df = term_counts
+                .iter()
+                .filter(|(counts, _)| counts.contains_key(term))
+                .count() as f64;
+            let idf = ((doc_count - df + 0.5) / (df + 0.5) + 1.0).ln();
+            (term.as_str(), idf)
+        })
+        .collect();
+
+    term_counts
+        .iter()
+        .map(|(counts, len)| {
+            let norm = K1 * (1.0 - B + B * *len as f64 / avg_len.max(1.0));
+            idf.iter()
+                .filter_map(|(term, idf)| {
+                    let tf = *counts.get(*term)? as f64;
+                    Some(idf * tf * (K1 + 1.0) / (tf + norm))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// 将文本切分为小写词项
+///
+/// 连续的字母数字作为一个词；中日韩文本没有空格分词，连续片段按相邻两字切分（bigram），
+/// 单个字符的片段保留为单字。
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    for ch in text.chars() {
+        if is_cjk(ch) {
+            flush_word(&mut word, &mut tokens);
+            cjk_run.push(ch);
+        } else {
+            flush_cjk(&mut cjk_run, &mut tokens);
+            if ch.is_alphanumeric() {
+                word.extend(ch.to_lowercase());
+            } else {
+                flush_word(&mut word, &mut tokens);
+            }
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_cjk(&mut cjk_run, &mut tokens);
+    tokens
+}
+
+fn flush_word(word: &mut String, tokens: &mut Vec<String>) {
+    if !word.is_empty() {
+        tokens.push(std::mem::take(word));
+    }
+}
+
+fn flush_cjk(run: &mut Vec<char>, tokens: &mut Vec<String>) {
+    match run.len() {
+        0 => {}
+        1 => tokens.push(run[0].to_string()),
+        _ => tokens.extend(run.windows(2).map(|pair| pair.iter().collect::<String>())),
+    }
+    run.clear();
+}
+
+pub(crate) fn is_cjk(ch: char) -> bool {
+    matches!(ch as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn tokenizes_words_and_cjk_bigrams() {
+        assert_eq!(
+            tokenize("Rust async-await 2024"),
+            ["rust", "async", "await", "2024"]
+        );
+        assert_eq!(tokenize("异步编程"), ["异步", "步编", "编程"]);
+        assert_eq!(tokenize("学 Rust"), ["学", "rust"]);
+        assert_eq!(tokenize("用Tokio写服务"), ["用", "tokio", "写服", "服务"]);
+        assert!(tokenize("  ,.!  ").is_empty());
+    }
+
+    #[test]
+    fn scores_matching_documents_higher() {
+        let documents = strings(&[
+            "Python web framework",
+            "Rust async runtime tokio",
+            "Rust book",
+        ]);
+        let scores = bm25_scores("rust async", &documents);
+        assert_eq!(scores[0], 0.0);
+        assert!(scores[1] > scores[2]);
+        assert!(scores[2] > 0.0);
+    }
+
+    #[test]
+    fn rarer_terms_weigh_more() {
+        let documents = strings(&["rust tokio", "rust serde", "rust axum"]);
+        let scores = bm25_scores("rust tokio", &documents);
+        assert!(scores[0] > scores[1]);
+        assert_eq!(scores[1], scores[2]);
+    }
+
+    #[test]
+    fn ranks_chinese_queries_by_bigrams() {
+        let documents = strings(&["Python 教程", "Rust 异步编程入门", "异步 IO 简介"]);
+        let scores = bm25_scores("异步编程", &documents);
+        assert_eq!(scores[0], 0.0);
+        assert!(scores[1] > scores[2]);
+    }
+
+    #[test]
+    fn empty_query_scores_zero() {
+        assert_eq!(bm25_scores("", &strings(&["a", "b"])), [0.0, 0.0]);
+        assert!(bm25_scores("rust", &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn reranker_normalizes_to_max_score() {
+        let results = Bm25Reranker::new()
+            .rerank(
+                "rust",
+                strings(&["go", "rust rust", "rust"]),
+                &RerankOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(results[0].relevance_score, 1.0);
+        assert!(
+            results
+                .iter()
+                .all(|result| (0.0..=1.0).contains(&result.relevance_score))
+        );
+        assert_eq!(results.last().unwrap().index, 0);
+        assert_eq!(results.last().unwrap().relevance_score, 0.0);
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use super::{
+    reranker::{Reranker, fill_documents, finish_results},
+    types::{RerankOptions, RerankResult},
+};
+use crate::http::CircuitStatus;
+
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// 单个 (提供方, 模型, query, 文档) 组合的缓存键
+pub type ScoreKey = [u8; 32];
+
+/// 重排序得分缓存
+///
+/// 以提供方、模型、query 与文档内容的 SHA-256 为键缓存相关性得分，只保存得分而不保存文档本身；
+/// 得分与文档在同一请求中的位置无关，因此重叠的文档列表也能复用。
+pub struct ScoreCache {
+    scores: Mutex<LruCache<ScoreKey, f64>>,
+}
+
+impl ScoreCache {
+    /// 容量为 0 时返回 `None`，表示不缓存
+    pub fn new(capacity: usize) -> Option<Self> {
+        let capacity = NonZeroUsize::new(capacity)?;
+        Some(Self {
+            scores: Mutex::new(LruCache::new(capacity)),
+        })
+    }
+
+    pub fn key(provider: &str, model: &str, query: &str, document: &str) -> ScoreKey {
+        let mut hasher = Sha256::new();
+        // 以长度前缀分隔各字段，避免拼接产生歧义
+        for part in [provider, model, query, document] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part.as_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    pub fn get(&self, key: &ScoreKey) -> Option<f64> {
+        self.scores.lock().unwrap().get(key).copied()
+    }
+
+    pub fn put(&self, key: ScoreKey, score: f64) {
+        self.scores.lock().unwrap().put(key, score);
+    }
+}
+
+/// 带得分缓存的重排序包装
+///
+/// 只把未缓存的文档发送给内部实现（相同内容的文档只发送一次），再与缓存得分合并排序；
+/// 得分相同时保持原始顺序，因此重复请求的结果顺序一致。
+pub struct CachedReranker {
+    inner: Arc<dyn Reranker>,
+    cache: ScoreCache,
+}
+
+impl CachedReranker {
+    pub fn new(inner: Arc<dyn Reranker>, cache: ScoreCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl Reranker for CachedReranker {
+    fn provider(&self) -> &'static str {
+        self.inner.provider()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn uses_query_template(&self) -> bool {
+        self.inner.uses_query_template()
+    }
+
+    fn absolute_scores(&self) -> bool {
+        self.inner.absolute_scores()
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
+        let keys: Vec<ScoreKey> = documents
+            .iter()
+            .map(|document| {
+                ScoreCache::key(self.inner.provider(), self.inner.model(), query, document)
+            })
+            .collect();
+        let mut scores: HashMap<ScoreKey, f64> = HashMap::new();
+        let mut pending_keys = Vec::new();
+        let mut pending_documents = Vec::new();
+        for (key, document) in keys.iter().zip(&documents) {
+            if scores.contains_key(key) || pending_keys.contains(key) {
+                continue;
+            }
+            match self.cache.get(key) {
+                Some(score) => {
+                    scores.insert(*key, score);
+                }
+                None => {
+                    pending_keys.push(*key);
+                    pending_documents.push(document.clone());
+                }
+            }
+        }
+
+        debug!(
+            documents_count = keys.len(),
+            cached = scores.len(),
+            uncached = pending_documents.len(),
+            "Looked up rerank score cache"
+        );
+
+        // 需要全部文档的得分才能写入缓存，因此不向内部实现转发 top_n
+        if !pending_documents.is_empty() {
+            let full = RerankOptions::default();
+            for result in self.inner.rerank(query, pending_documents, &full).await? {
+                if let Some(key) = pending_keys.get(result.index) {
+                    self.cache.put(*key, result.relevance_score);
+                    scores.insert(*key, result.relevance_score);
+                }
+            }
+        }
+
+        let mut results: Vec<RerankResult> = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(index, key)| {
+                scores.get(key).map(|&relevance_score| RerankResult {
+                    index,
+                    relevance_score,
+                    document: None,
+                })
+            })
+            .collect();
+        finish_results(&mut results, options);
+        if options.return_documents {
+            fill_documents(&mut results, &documents);
+        }
+        Ok(results)
+    }
+
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.inner.circuit_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按文档长度打分并记录每次收到的文档与 top_n
+    struct CountingReranker {
+        calls: Mutex<Vec<(Vec<String>, Option<usize>)>>,
+    }
+
+    impl CountingReranker {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn sent(&self) -> Vec<Vec<String>> {
+            let calls = self.calls.lock().unwrap();
+            calls
+                .iter()
+                .map(|(documents, _)| documents.clone())
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl Reranker for CountingReranker {
+        fn provider(&self) -> &'static str {
+            "counting"
+        }
+
+        fn model(&self) -> &str {
+            "length"
+        }
+
+        async fn rerank(
+            &self,
+            _query: &str,
+            documents: Vec<String>,
+            options: &RerankOptions,
+        ) -> Result<Vec<RerankResult>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((documents.clone(), options.top_n));
+            let mut results: Vec<RerankResult> = documents
+                .iter()
+                .enumerate()
+                .map(|(index, document)| RerankResult {
+                    index,
+                    relevance_score: document.chars().count() as f64,
+                    document: None,
+                })
+                .collect();
+            finish_results(&mut results, options);
+            Ok(results)
+        }
+    }
+
+    fn cached(inner: &Arc<CountingReranker>) -> CachedReranker {
+        CachedReranker::new(inner.clone(), ScoreCache::new(100).unwrap())
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    fn ranked(results: &[RerankResult]) -> Vec<(usize, f64)> {
+        results
+            .iter()
+            .map(|result| (result.index, result.relevance_score))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn repeated_call_sends_nothing_upstream() {
+        let inner = CountingReranker::new();
+        let reranker = cached(&inner);
+        let documents = strings(&["a", "bbb", "cc"]);
+        let options = RerankOptions::default();
+
+        let first = reranker
+            .rerank("q", documents.clone(), &options)
+            .await
+            .unwrap();
+        let second = reranker.rerank("q", documents, &options).await.unwrap();
+        assert_eq!(ranked(&first), ranked(&second));
+        assert_eq!(inner.sent(), [strings(&["a", "bbb", "cc"])]);
+    }
+
+    #[tokio::test]
+    async fn overlapping_call_sends_only_uncached_documents() {
+        let inner = CountingReranker::new();
+        let reranker = cached(&inner);
+        let options = RerankOptions::default();
+
+        reranker
+            .rerank("q", strings(&["a", "bb"]), &options)
+            .await
+            .unwrap();
+        let results = reranker
+            .rerank("q", strings(&["bb", "cccc", "a"]), &options)
+            .await
+            .unwrap();
+        assert_eq!(ranked(&results), [(1, 4.0), (0, 2.0), (2, 1.0)]);
+        assert_eq!(inner.sent()[1], strings(&["cccc"]));
+    }
+
+    #[tokio::test]
+    async fn duplicate_documents_are_sent_once() {
+        let inner = CountingReranker::new();
+        let reranker = cached(&inner);
+
+        let results = reranker
+            .rerank("q", strings(&["xx", "y", "xx"]), &RerankOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(ranked(&results), [(0, 2.0), (2, 2.0), (1, 1.0)]);
+        assert_eq!(inner.sent(), [strings(&["xx", "y"])]);
+    }
+
+    #[tokio::test]
+    async fn matches_uncached_order_and_indices() {
+        let inner = CountingReranker::new();
+        let reranker = cached(&inner);
+        let documents = strings(&["bb", "a", "cc", "ddd", "e"]);
+        let options = RerankOptions {
+            top_n: None,
+            return_documents: true,
+        };
+
+        let uncached = inner
+            .rerank("q", documents.clone(), &RerankOptions::default())
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            let results = reranker
+                .rerank("q", documents.clone(), &options)
+                .await
+                .unwrap();
+            assert_eq!(ranked(&results), ranked(&uncached));
+            for result in &results {
+                assert_eq!(result.document.as_ref(), Some(&documents[result.index]));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_top_n_after_merging_cached_scores() {
+        let inner = CountingReranker::new();
+        let reranker = cached(&inner);
+        reranker
+            .rerank("q", strings(&["a", "bbb"]), &RerankOptions::default())
+            .await
+            .unwrap();
+
+        let options = RerankOptions {
+            top_n: Some(2),
+            return_documents: false,
+        };
+        let results = reranker
+            .rerank("q", strings(&["a", "bbb", "cc"]), &options)
+            .await
+            .unwrap();
+        assert_eq!(ranked(&results), [(1, 3.0), (2, 2.0)]);
+        // 未缓存的文档需要完整得分，top_n 不转发给内部实现
+        assert_eq!(inner.calls.lock().unwrap()[1], (strings(&["cc"]), None));
+    }
+
+    #[tokio::test]
+    async fn different_query_is_not_served_from_cache() {
+        let inner = CountingReranker::new();
+        let reranker = cached(&inner);
+        let documents = strings(&["a", "bb"]);
+        let options = RerankOptions::default();
+
+        reranker
+            .rerank("q1", documents.clone(), &options)
+            .await
+            .unwrap();
+        reranker
+            .rerank("q2", documents.clone(), &options)
+            .await
+            .unwrap();
+        assert_eq!(inner.sent(), [documents.clone(), documents]);
+    }
+
+    #[test]
+    fn keys_separate_every_field() {
+        let key = ScoreCache::key("tei", "model-a", "query", "doc");
+        assert_eq!(key, ScoreCache::key("tei", "model-a", "query", "doc"));
+        assert_ne!(key, ScoreCache::key("jina", "model-a", "query", "doc"));
+        assert_ne!(key, ScoreCache::key("tei", "model-b", "query", "doc"));
+        assert_ne!(key, ScoreCache::key("tei", "model-a", "query2", "doc"));
+        // 长度前缀避免字段拼接后相同
+        assert_ne!(
+            ScoreCache::key("tei", "model-a", "ab", "c"),
+            ScoreCache::key("tei", "model-a", "a", "bc")
+        );
+    }
+
+    #[test]
+    fn zero_capacity_disables_cache() {
+        assert!(ScoreCache::new(0).is_none());
+        let cache = ScoreCache::new(1).unwrap();
+        let (first, second) = ([1; 32], [2; 32]);
+        cache.put(first, 0.5);
+        cache.put(second, 0.7);
+        assert_eq!(cache.get(&first), None);
+        assert_eq!(cache.get(&second), Some(0.7));
+    }
+}
@@ -1,16 +1,28 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use reqwest::Client;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::http::{CircuitStatus, OutboundClient};
 use tracing::{debug, info};
 
-use super::types::{RerankRequest, RerankResponse, RerankResult};
+use super::{
+    provider::RerankConfig,
+    reranker::{Reranker, finish_results},
+    types::{RerankOptions, RerankRequest, RerankResponse, RerankResult},
+};
 
-const RERANK_API_ENDPOINT: &str = "https://api.siliconflow.cn/v1/rerank";
-const DEFAULT_MODEL: &str = "Qwen/Qwen3-Reranker-8B";
+pub const RERANK_API_ENDPOINT: &str = "https://api.siliconflow.cn/v1/rerank";
+pub const DEFAULT_MODEL: &str = "Qwen/Qwen3-Reranker-8B";
+pub const DEFAULT_TIMEOUT_SECS: u64 = 15;
 
+/// 轨迹流动（SiliconFlow）重排序客户端
 #[derive(Clone)]
 pub struct RerankClient {
     api_key: String,
-    http: Client,
+    model: String,
+    endpoint: String,
+    http: OutboundClient,
 }
 
 impl RerankClient {
@@ -20,18 +32,43 @@ impl RerankClient {
         let api_key = std::env::var("SILICONFLOW_API_KEY")
             .context("SILICONFLOW_API_KEY environment variable not set")?;
 
-        Ok(Self {
-            api_key,
-            http: Client::new(),
-        })
+        Ok(Self::with_api_key(api_key))
     }
 
     /// 使用指定的 API 密钥创建 RerankClient
     pub fn with_api_key(api_key: String) -> Self {
-        Self {
+        Self::from_config(RerankConfig::with_api_key(api_key))
+            .expect("default rerank http config is valid")
+    }
+
+    /// 根据配置创建 RerankClient（端点、模型、超时与重试均可自定义）
+    pub fn from_config(config: RerankConfig) -> Result<Self> {
+        let api_key = config
+            .api_key
+            .context("SiliconFlow reranker requires an API key")?;
+        let http = OutboundClient::new("rerank", config.http, Client::builder())?;
+
+        Ok(Self {
             api_key,
-            http: Client::new(),
-        }
+            model: config.model,
+            endpoint: config.endpoint,
+            http,
+        })
+    }
+
+    pub fn http(&self) -> &OutboundClient {
+        &self.http
+    }
+}
+
+#[async_trait]
+impl Reranker for RerankClient {
+    fn provider(&self) -> &'static str {
+        "siliconflow"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
     }
 
     /// 对文档进行重排序
@@ -42,7 +79,12 @@ impl RerankClient {
     ///
     /// # 返回
     /// 返回按相关性得分排序的结果列表（从高到低）
-    pub async fn rerank(&self, query: &str, documents: Vec<String>) -> Result<Vec<RerankResult>> {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
         if documents.is_empty() {
             debug!("Empty documents list, returning empty results");
             return Ok(Vec::new());
@@ -51,56 +93,200 @@ impl RerankClient {
         debug!(
             query = %query,
             documents_count = documents.len(),
-            model = DEFAULT_MODEL,
+            model = %self.model,
             "Sending rerank request to SiliconFlow API"
         );
 
         let request = RerankRequest {
-            model: DEFAULT_MODEL.to_string(),
+            model: self.model.clone(),
             query: query.to_string(),
             documents,
+            top_n: options.top_n,
+            return_documents: Some(options.return_documents),
         };
-
-        let response = self
-            .http
-            .post(RERANK_API_ENDPOINT)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send rerank request")?
-            .error_for_status()
-            .context("Rerank API returned error status")?
-            .json::<RerankResponse>()
-            .await
-            .context("Failed to decode rerank response")?;
+        let response: RerankResponse =
+            post_rerank(&self.http, &self.endpoint, Some(&self.api_key), &request).await?;
 
         info!(
-            id = %response.id,
+            id = ?response.id,
             results_count = response.results.len(),
             "Received rerank response from SiliconFlow API"
         );
 
         debug!(
-            response_id = %response.id,
+            response_id = ?response.id,
             results = ?response.results,
             "Complete rerank response details"
         );
 
-        // 按相关性得分从高到低排序
         let mut results = response.results;
-        results.sort_by(|a, b| {
-            b.relevance_score
-                .partial_cmp(&a.relevance_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        finish_results(&mut results, options);
+        Ok(results)
+    }
 
-        debug!(
-            sorted_results_count = results.len(),
-            "Sorted rerank results by relevance score (descending)"
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.http.circuit_status()
+    }
+}
+
+/// 向重排序端点发送 JSON 请求，各 HTTP 提供方共用
+pub(super) async fn post_rerank<T: DeserializeOwned>(
+    http: &OutboundClient,
+    endpoint: &str,
+    api_key: Option<&str>,
+    body: &impl Serialize,
+) -> Result<T> {
+    let mut request = http
+        .inner()
+        .post(endpoint)
+        .header("Content-Type", "application/json")
+        .json(body);
+    if let Some(api_key) = api_key {
+        request = request.header("Authorization", format!("Bearer {api_key}"));
+    }
+
+    // 重排序没有副作用，失败时可安全重试
+    http.send_idempotent(request)
+        .await
+        .context("Failed to send rerank request")?
+        .error_for_status()
+        .context("Rerank API returned error status")?
+        .json::<T>()
+        .await
+        .context("Failed to decode rerank response")
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{Json, Router, http::HeaderMap, http::StatusCode, routing::post};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{http::HttpConfig, rerank::RerankProvider};
+
+    /// 模拟重排序端点收到的请求：Authorization 头与 JSON 请求体
+    pub(in crate::rerank) type Requests = Arc<Mutex<Vec<(Option<String>, Value)>>>;
+
+    /// 以固定状态码与响应体应答 `/rerank` 的模拟服务，返回使用该端点的配置与请求记录
+    pub(in crate::rerank) async fn mock_provider(
+        provider: RerankProvider,
+        status: StatusCode,
+        body: Value,
+    ) -> (RerankConfig, Requests) {
+        let requests = Requests::default();
+        let recorded = requests.clone();
+        let router = Router::new().route(
+            "/rerank",
+            post(move |headers: HeaderMap, Json(request): Json<Value>| {
+                let authorization = headers
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                recorded.lock().unwrap().push((authorization, request));
+                let body = body.clone();
+                async move { (status, Json(body)) }
+            }),
         );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
 
-        Ok(results)
+        let config = RerankConfig {
+            provider,
+            model: provider.default_model().to_string(),
+            endpoint: format!("http://{addr}/rerank"),
+            http: HttpConfig {
+                max_retries: 0,
+                ..HttpConfig::default()
+            },
+            ..RerankConfig::with_api_key("test-key".to_string())
+        };
+        (config, requests)
+    }
+
+    pub(in crate::rerank) fn documents() -> Vec<String> {
+        vec!["alpha".to_string(), "beta".to_string(), "gamma".to_string()]
+    }
+
+    #[tokio::test]
+    async fn sends_model_top_n_and_parses_documents() {
+        let (config, requests) = mock_provider(
+            RerankProvider::SiliconFlow,
+            StatusCode::OK,
+            json!({
+                "id": "req-1",
+                "results": [
+                    { "index": 0, "relevance_score": 0.2, "document": "alpha" },
+                    { "index": 1, "relevance_score": 0.9, "document": { "text": "beta" } },
+                ],
+            }),
+        )
+        .await;
+        let options = RerankOptions {
+            top_n: Some(2),
+            return_documents: true,
+        };
+
+        let results = RerankClient::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &options)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            (results[0].index, results[0].document.as_deref()),
+            (1, Some("beta"))
+        );
+        assert_eq!(
+            (results[1].index, results[1].document.as_deref()),
+            (0, Some("alpha"))
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0.as_deref(), Some("Bearer test-key"));
+        assert_eq!(
+            requests[0].1,
+            json!({
+                "model": DEFAULT_MODEL,
+                "query": "query",
+                "documents": ["alpha", "beta", "gamma"],
+                "top_n": 2,
+                "return_documents": true,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_error_status_to_error() {
+        let (config, requests) = mock_provider(
+            RerankProvider::SiliconFlow,
+            StatusCode::UNAUTHORIZED,
+            json!({ "message": "invalid api key" }),
+        )
+        .await;
+
+        let err = RerankClient::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &RerankOptions::default())
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("error status"), "{err:#}");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn skips_request_without_documents() {
+        let (config, requests) =
+            mock_provider(RerankProvider::SiliconFlow, StatusCode::OK, json!({})).await;
+
+        let results = RerankClient::from_config(config)
+            .unwrap()
+            .rerank("query", Vec::new(), &RerankOptions::default())
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+        assert!(requests.lock().unwrap().is_empty());
     }
 }
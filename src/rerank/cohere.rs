@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use tracing::debug;
+
+use super::{
+    client::post_rerank,
+    provider::RerankConfig,
+    reranker::{Reranker, fill_documents, finish_results},
+    types::{RerankOptions, RerankRequest, RerankResponse, RerankResult},
+};
+use crate::http::{CircuitStatus, OutboundClient};
+
+pub const DEFAULT_ENDPOINT: &str = "https://api.cohere.com/v2/rerank";
+pub const DEFAULT_MODEL: &str = "rerank-v3.5";
+
+/// Cohere `/v2/rerank` 及兼容接口（如 vLLM、Voyage 网关）的重排序客户端
+#[derive(Clone)]
+pub struct CohereReranker {
+    api_key: String,
+    model: String,
+    endpoint: String,
+    http: OutboundClient,
+}
+
+impl CohereReranker {
+    pub fn from_config(config: RerankConfig) -> Result<Self> {
+        let api_key = config
+            .api_key
+            .context("Cohere reranker requires an API key")?;
+        let http = OutboundClient::new("rerank", config.http, Client::builder())?;
+
+        Ok(Self {
+            api_key,
+            model: config.model,
+            endpoint: config.endpoint,
+            http,
+        })
+    }
+}
+
+#[async_trait]
+impl Reranker for CohereReranker {
+    fn provider(&self) -> &'static str {
+        "cohere"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(
+            documents_count = documents.len(),
+            model = %self.model,
+            "Sending rerank request to Cohere API"
+        );
+        // Cohere v2 不再支持 return_documents，需要原文时在本地补齐
+        let originals = options.return_documents.then(|| documents.clone());
+        let request = RerankRequest {
+            model: self.model.clone(),
+            query: query.to_string(),
+            documents,
+            top_n: options.top_n,
+            return_documents: None,
+        };
+        let response: RerankResponse =
+            post_rerank(&self.http, &self.endpoint, Some(&self.api_key), &request).await?;
+
+        let mut results = response.results;
+        finish_results(&mut results, options);
+        if let Some(originals) = originals {
+            fill_documents(&mut results, &originals);
+        }
+        Ok(results)
+    }
+
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.http.circuit_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::rerank::{
+        RerankProvider,
+        client::tests::{documents, mock_provider},
+    };
+
+    #[tokio::test]
+    async fn omits_return_documents_and_fills_them_locally() {
+        let (config, requests) = mock_provider(
+            RerankProvider::Cohere,
+            StatusCode::OK,
+            json!({
+                "id": "req-1",
+                "results": [
+                    { "index": 2, "relevance_score": 0.7 },
+                    { "index": 0, "relevance_score": 0.4 },
+                ],
+                "meta": { "billed_units": { "search_units": 1 } },
+            }),
+        )
+        .await;
+        let options = RerankOptions {
+            top_n: Some(2),
+            return_documents: true,
+        };
+
+        let results = CohereReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &options)
+            .await
+            .unwrap();
+        assert_eq!(
+            (results[0].index, results[0].document.as_deref()),
+            (2, Some("gamma"))
+        );
+        assert_eq!(
+            (results[1].index, results[1].document.as_deref()),
+            (0, Some("alpha"))
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0.as_deref(), Some("Bearer test-key"));
+        assert_eq!(
+            requests[0].1,
+            json!({
+                "model": DEFAULT_MODEL,
+                "query": "query",
+                "documents": ["alpha", "beta", "gamma"],
+                "top_n": 2,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_server_error_to_error() {
+        let (config, _) = mock_provider(
+            RerankProvider::Cohere,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            json!({ "message": "internal error" }),
+        )
+        .await;
+
+        let result = CohereReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &RerankOptions::default())
+            .await;
+        assert!(result.is_err());
+    }
+}
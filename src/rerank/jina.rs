@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use tracing::debug;
+
+use super::{
+    client::post_rerank,
+    provider::RerankConfig,
+    reranker::{Reranker, finish_results},
+    types::{RerankOptions, RerankRequest, RerankResponse, RerankResult},
+};
+use crate::http::{CircuitStatus, OutboundClient};
+
+pub const DEFAULT_ENDPOINT: &str = "https://api.jina.ai/v1/rerank";
+pub const DEFAULT_MODEL: &str = "jina-reranker-v2-base-multilingual";
+
+/// Jina `/v1/rerank` 及兼容接口的重排序客户端
+#[derive(Clone)]
+pub struct JinaReranker {
+    api_key: String,
+    model: String,
+    endpoint: String,
+    http: OutboundClient,
+}
+
+impl JinaReranker {
+    pub fn from_config(config: RerankConfig) -> Result<Self> {
+        let api_key = config
+            .api_key
+            .context("Jina reranker requires an API key")?;
+        let http = OutboundClient::new("rerank", config.http, Client::builder())?;
+
+        Ok(Self {
+            api_key,
+            model: config.model,
+            endpoint: config.endpoint,
+            http,
+        })
+    }
+}
+
+#[async_trait]
+impl Reranker for JinaReranker {
+    fn provider(&self) -> &'static str {
+        "jina"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(
+            documents_count = documents.len(),
+            model = %self.model,
+            "Sending rerank request to Jina API"
+        );
+        let request = RerankRequest {
+            model: self.model.clone(),
+            query: query.to_string(),
+            documents,
+            top_n: options.top_n,
+            return_documents: Some(options.return_documents),
+        };
+        // Jina 的响应没有 id，结果中可能附带文档内容，只读取 index 与得分
+        let response: RerankResponse =
+            post_rerank(&self.http, &self.endpoint, Some(&self.api_key), &request).await?;
+
+        let mut results = response.results;
+        finish_results(&mut results, options);
+        Ok(results)
+    }
+
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.http.circuit_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::rerank::{
+        RerankProvider,
+        client::tests::{documents, mock_provider},
+    };
+
+    #[tokio::test]
+    async fn sends_model_top_n_and_parses_response_without_id() {
+        let (config, requests) = mock_provider(
+            RerankProvider::Jina,
+            StatusCode::OK,
+            json!({
+                "model": DEFAULT_MODEL,
+                "usage": { "total_tokens": 12 },
+                "results": [
+                    { "index": 1, "relevance_score": 0.8, "document": { "text": "beta" } },
+                    { "index": 2, "relevance_score": 0.1, "document": { "text": "gamma" } },
+                ],
+            }),
+        )
+        .await;
+        let options = RerankOptions {
+            top_n: Some(2),
+            return_documents: false,
+        };
+
+        let results = JinaReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &options)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].index, 1);
+        assert_eq!(results[0].relevance_score, 0.8);
+        assert_eq!(results[1].document.as_deref(), Some("gamma"));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0.as_deref(), Some("Bearer test-key"));
+        assert_eq!(
+            requests[0].1,
+            json!({
+                "model": DEFAULT_MODEL,
+                "query": "query",
+                "documents": ["alpha", "beta", "gamma"],
+                "top_n": 2,
+                "return_documents": false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_client_error_to_error() {
+        let (config, _) = mock_provider(
+            RerankProvider::Jina,
+            StatusCode::UNPROCESSABLE_ENTITY,
+            json!({ "detail": "invalid model" }),
+        )
+        .await;
+
+        let result = JinaReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &RerankOptions::default())
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -1,5 +1,18 @@
+pub mod batch;
+pub mod bm25;
+pub mod cache;
 pub mod client;
+pub mod cohere;
+pub mod jina;
+pub mod provider;
+pub mod reranker;
+pub mod tei;
+pub mod template;
 pub mod types;
 
+pub use bm25::Bm25Reranker;
 pub use client::RerankClient;
-pub use types::{RerankRequest, RerankResponse, RerankResult};
+pub use provider::{RerankConfig, RerankProvider, build_reranker};
+pub use reranker::Reranker;
+pub use template::QueryTemplates;
+pub use types::{RerankOptions, RerankRequest, RerankResponse, RerankResult};
@@ -0,0 +1,173 @@
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::Result;
+
+use super::{
+    batch::{self, BatchedReranker},
+    bm25::Bm25Reranker,
+    cache::{self, CachedReranker, ScoreCache},
+    client::{self, DEFAULT_TIMEOUT_SECS, RerankClient},
+    cohere::{self, CohereReranker},
+    jina::{self, JinaReranker},
+    reranker::Reranker,
+    tei::{self, TeiReranker},
+};
+use crate::http::HttpConfig;
+
+/// 重排序服务提供方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RerankProvider {
+    /// 轨迹流动（SiliconFlow）
+    SiliconFlow,
+    /// Cohere `/v2/rerank` 及兼容接口
+    Cohere,
+    /// Jina `/v1/rerank` 及兼容接口
+    Jina,
+    /// HuggingFace text-embeddings-inference 的 `/rerank`
+    Tei,
+    /// 进程内 BM25，不发送网络请求
+    Bm25,
+}
+
+impl RerankProvider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RerankProvider::SiliconFlow => "siliconflow",
+            RerankProvider::Cohere => "cohere",
+            RerankProvider::Jina => "jina",
+            RerankProvider::Tei => "tei",
+            RerankProvider::Bm25 => "bm25",
+        }
+    }
+
+    /// 默认端点；自部署的 TEI 与本地 BM25 没有默认值
+    pub fn default_endpoint(self) -> Option<&'static str> {
+        match self {
+            RerankProvider::SiliconFlow => Some(client::RERANK_API_ENDPOINT),
+            RerankProvider::Cohere => Some(cohere::DEFAULT_ENDPOINT),
+            RerankProvider::Jina => Some(jina::DEFAULT_ENDPOINT),
+            RerankProvider::Tei | RerankProvider::Bm25 => None,
+        }
+    }
+
+    pub fn default_model(self) -> &'static str {
+        match self {
+            RerankProvider::SiliconFlow => client::DEFAULT_MODEL,
+            RerankProvider::Cohere => cohere::DEFAULT_MODEL,
+            RerankProvider::Jina => jina::DEFAULT_MODEL,
+            RerankProvider::Tei => tei::DEFAULT_MODEL,
+            RerankProvider::Bm25 => "bm25",
+        }
+    }
+
+    /// 单次请求的默认最大文档数
+    pub fn default_max_documents(self) -> usize {
+        match self {
+            RerankProvider::Tei => batch::DEFAULT_TEI_MAX_DOCUMENTS,
+            _ => batch::DEFAULT_MAX_DOCUMENTS,
+        }
+    }
+
+    /// 是否返回原始 logits，需要经 sigmoid 映射到 0~1；TEI 以 `raw_scores` 请求原始得分
+    pub fn returns_logits(self) -> bool {
+        self == RerankProvider::Tei
+    }
+
+    /// 托管服务需要 API 密钥，自部署的 TEI 可不设置
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, RerankProvider::Tei | RerankProvider::Bm25)
+    }
+
+    /// 是否在进程内计算，不需要端点
+    pub fn is_local(self) -> bool {
+        self == RerankProvider::Bm25
+    }
+}
+
+impl fmt::Display for RerankProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RerankProvider {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "siliconflow" => Ok(RerankProvider::SiliconFlow),
+            "cohere" => Ok(RerankProvider::Cohere),
+            "jina" => Ok(RerankProvider::Jina),
+            "tei" => Ok(RerankProvider::Tei),
+            "bm25" => Ok(RerankProvider::Bm25),
+            _ => Err("expected `siliconflow`, `cohere`, `jina`, `tei` or `bm25`".to_string()),
+        }
+    }
+}
+
+/// 重排序配置
+#[derive(Debug, Clone)]
+pub struct RerankConfig {
+    pub provider: RerankProvider,
+    pub api_key: Option<String>,
+    pub model: String,
+    pub endpoint: String,
+    /// 超时、重试与熔断配置
+    pub http: HttpConfig,
+    /// 得分缓存最多保留的 (query, 文档) 组合数，为 0 时不缓存
+    pub cache_capacity: usize,
+    /// 单次请求最多发送的文档数，超出时分批并发请求
+    pub max_documents: usize,
+    /// 单个文档的估算 token 上限，超出部分截断
+    pub max_document_tokens: usize,
+    /// 是否对提供方返回的得分做 sigmoid 映射，默认取 [`RerankProvider::returns_logits`]
+    pub sigmoid_scores: bool,
+}
+
+impl RerankConfig {
+    /// 使用 SiliconFlow 的默认端点、模型与超时创建配置
+    pub fn with_api_key(api_key: String) -> Self {
+        Self {
+            provider: RerankProvider::SiliconFlow,
+            api_key: Some(api_key),
+            model: client::DEFAULT_MODEL.to_string(),
+            endpoint: client::RERANK_API_ENDPOINT.to_string(),
+            http: HttpConfig::with_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            cache_capacity: cache::DEFAULT_CAPACITY,
+            max_documents: batch::DEFAULT_MAX_DOCUMENTS,
+            max_document_tokens: batch::DEFAULT_MAX_DOCUMENT_TOKENS,
+            sigmoid_scores: false,
+        }
+    }
+}
+
+/// 按配置创建重排序实现
+///
+/// 远程提供方依次包装分批请求与得分缓存（`cache_capacity` 大于 0 时），
+/// 缓存在外层，只有未缓存的文档会进入分批，缓存的得分已经过 sigmoid 映射（如启用）。
+pub fn build_reranker(config: RerankConfig) -> Result<Arc<dyn Reranker>> {
+    if config.provider.is_local() {
+        return Ok(Arc::new(Bm25Reranker::new()));
+    }
+    let cache_capacity = config.cache_capacity;
+    let max_documents = config.max_documents;
+    let max_document_tokens = config.max_document_tokens;
+    let sigmoid_scores = config.sigmoid_scores;
+    let reranker: Arc<dyn Reranker> = match config.provider {
+        RerankProvider::SiliconFlow => Arc::new(RerankClient::from_config(config)?),
+        RerankProvider::Cohere => Arc::new(CohereReranker::from_config(config)?),
+        RerankProvider::Jina => Arc::new(JinaReranker::from_config(config)?),
+        RerankProvider::Tei => Arc::new(TeiReranker::from_config(config)?),
+        RerankProvider::Bm25 => Arc::new(Bm25Reranker::new()),
+    };
+    let reranker: Arc<dyn Reranker> = Arc::new(BatchedReranker::new(
+        reranker,
+        max_documents,
+        max_document_tokens,
+        sigmoid_scores,
+    ));
+    Ok(match ScoreCache::new(cache_capacity) {
+        Some(cache) => Arc::new(CachedReranker::new(reranker, cache)),
+        None => reranker,
+    })
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::debug;
+
+use super::types::{RerankOptions, RerankResult};
+use crate::http::CircuitStatus;
+
+/// 重排序能力的统一接口，搜索与深度搜索只依赖该 trait
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// 提供方名称，用于日志、缓存键与健康检查
+    fn provider(&self) -> &'static str;
+
+    fn model(&self) -> &str;
+
+    /// 对文档进行重排序
+    ///
+    /// 返回按相关性得分从高到低排序的结果，`index` 对应 `documents` 中的位置；
+    /// 提供方未返回得分的文档不出现在结果中。设置 `options.top_n` 时最多返回 N 条。
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>>;
+
+    /// 是否使用带搜索类型说明的增强 query；词法实现按词匹配打分，需要原始搜索词
+    fn uses_query_template(&self) -> bool {
+        true
+    }
+
+    /// 得分是否有跨查询可比的固定尺度；词法实现的得分按本次最高分归一化，不适合用作阈值
+    fn absolute_scores(&self) -> bool {
+        true
+    }
+
+    /// 出站请求的熔断器状态，本地实现返回空列表
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        Vec::new()
+    }
+}
+
+/// 排序后按 `top_n` 截断，提供方在服务端截断时同样适用
+pub(crate) fn finish_results(results: &mut Vec<RerankResult>, options: &RerankOptions) {
+    sort_by_score(results);
+    if let Some(top_n) = options.top_n {
+        results.truncate(top_n);
+    }
+}
+
+/// 提供方不支持返回原文时，按 `index` 从请求文档中补齐
+pub(crate) fn fill_documents(results: &mut [RerankResult], documents: &[String]) {
+    for result in results {
+        if result.document.is_none() {
+            result.document = documents.get(result.index).cloned();
+        }
+    }
+}
+
+/// 按相关性得分从高到低排序，得分相同时按原始位置排序
+fn sort_by_score(results: &mut [RerankResult]) {
+    results.sort_by(|a, b| {
+        b.relevance_score
+            .partial_cmp(&a.relevance_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.index.cmp(&b.index))
+    });
+
+    debug!(
+        sorted_results_count = results.len(),
+        "Sorted rerank results by relevance score (descending)"
+    );
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use tracing::debug;
+
+use super::{
+    client::post_rerank,
+    provider::RerankConfig,
+    reranker::{Reranker, finish_results},
+    types::{RerankOptions, RerankResult, TeiRerankRequest, TeiRerankResult},
+};
+use crate::http::{CircuitStatus, OutboundClient};
+
+/// TEI 每个实例只加载一个模型，该名称仅用于日志与缓存键
+pub const DEFAULT_MODEL: &str = "tei";
+
+/// HuggingFace text-embeddings-inference `/rerank` 客户端
+#[derive(Clone)]
+pub struct TeiReranker {
+    api_key: Option<String>,
+    model: String,
+    endpoint: String,
+    http: OutboundClient,
+}
+
+impl TeiReranker {
+    pub fn from_config(config: RerankConfig) -> Result<Self> {
+        let http = OutboundClient::new("rerank", config.http, Client::builder())?;
+
+        Ok(Self {
+            api_key: config.api_key,
+            model: config.model,
+            endpoint: config.endpoint,
+            http,
+        })
+    }
+}
+
+#[async_trait]
+impl Reranker for TeiReranker {
+    fn provider(&self) -> &'static str {
+        "tei"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: Vec<String>,
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        debug!(
+            documents_count = documents.len(),
+            endpoint = %self.endpoint,
+            "Sending rerank request to TEI"
+        );
+        let request = TeiRerankRequest {
+            query: query.to_string(),
+            texts: documents,
+            // 超出模型长度的文本由服务端截断，而不是整体报错；TEI 不支持 top_n，在本地截断
+            truncate: true,
+            raw_scores: true,
+            return_text: options.return_documents,
+        };
+        let response: Vec<TeiRerankResult> = post_rerank(
+            &self.http,
+            &self.endpoint,
+            self.api_key.as_deref(),
+            &request,
+        )
+        .await?;
+
+        let mut results: Vec<RerankResult> = response
+            .into_iter()
+            .map(|result| RerankResult {
+                index: result.index,
+                relevance_score: result.score,
+                document: result.text,
+            })
+            .collect();
+        finish_results(&mut results, options);
+        Ok(results)
+    }
+
+    fn circuit_status(&self) -> Vec<CircuitStatus> {
+        self.http.circuit_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::rerank::{
+        RerankProvider,
+        client::tests::{documents, mock_provider},
+    };
+
+    #[tokio::test]
+    async fn sends_texts_with_raw_scores_and_truncates_locally() {
+        let (mut config, requests) = mock_provider(
+            RerankProvider::Tei,
+            StatusCode::OK,
+            json!([
+                { "index": 0, "score": -1.5 },
+                { "index": 2, "score": 3.25 },
+                { "index": 1, "score": 0.5 },
+            ]),
+        )
+        .await;
+        config.api_key = None;
+        let options = RerankOptions {
+            top_n: Some(2),
+            return_documents: false,
+        };
+
+        let results = TeiReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &options)
+            .await
+            .unwrap();
+        let ranked: Vec<(usize, f64)> = results
+            .iter()
+            .map(|result| (result.index, result.relevance_score))
+            .collect();
+        assert_eq!(ranked, [(2, 3.25), (1, 0.5)]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0, None);
+        assert_eq!(
+            requests[0].1,
+            json!({
+                "query": "query",
+                "texts": ["alpha", "beta", "gamma"],
+                "truncate": true,
+                "raw_scores": true,
+                "return_text": false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_text_when_requested() {
+        let (config, requests) = mock_provider(
+            RerankProvider::Tei,
+            StatusCode::OK,
+            json!([{ "index": 1, "score": 0.5, "text": "beta" }]),
+        )
+        .await;
+        let options = RerankOptions {
+            top_n: None,
+            return_documents: true,
+        };
+
+        let results = TeiReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &options)
+            .await
+            .unwrap();
+        assert_eq!(results[0].document.as_deref(), Some("beta"));
+        assert_eq!(requests.lock().unwrap()[0].1["return_text"], true);
+    }
+
+    #[tokio::test]
+    async fn maps_server_error_to_error() {
+        let (config, _) = mock_provider(
+            RerankProvider::Tei,
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({ "error": "model is loading" }),
+        )
+        .await;
+
+        let result = TeiReranker::from_config(config)
+            .unwrap()
+            .rerank("query", documents(), &RerankOptions::default())
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// 默认模板，与未配置时的中文提示保持一致
+pub const DEFAULT_TEMPLATE: &str =
+    "用户使用搜索引擎搜索，正在进行{category}的类型的搜索，搜索目标是\"{query}\"";
+/// 英文搜索（`language` 为 `en` 或 `en-*`）使用的内置模板
+pub const DEFAULT_ENGLISH_TEMPLATE: &str =
+    "A user is searching the web in the {category} category. Search target: \"{query}\"";
+
+/// 重排序 query 模板
+///
+/// 模板支持 `{query}`、`{category}` 与 `{date}`（UTC 日期，`YYYY-MM-DD`）占位符。
+/// 选择顺序：单次调用的指令 > 分类模板（先匹配 `分类:语言`，再匹配单独的分类）> 语言模板 > 默认模板。
+#[derive(Debug, Clone)]
+pub struct QueryTemplates {
+    pub default: String,
+    /// 按 searXNG 分类（如 `news`、`it`）覆盖；键也可以是 `分类:语言`（如 `it:zh`），语言部分为小写
+    pub by_category: HashMap<String, String>,
+    /// 按语言代码覆盖，键为小写，如 `en`、`zh-cn`；未命中完整代码时回退到主语言
+    pub by_language: HashMap<String, String>,
+}
+
+impl Default for QueryTemplates {
+    fn default() -> Self {
+        Self {
+            default: DEFAULT_TEMPLATE.to_string(),
+            by_category: HashMap::new(),
+            by_language: HashMap::from([("en".to_string(), DEFAULT_ENGLISH_TEMPLATE.to_string())]),
+        }
+    }
+}
+
+impl QueryTemplates {
+    /// 按分类与语言选择模板并替换占位符
+    ///
+    /// `instruction` 为单次调用的覆盖模板；其中不含 `{query}` 时视为纯指令，原始搜索词另起一行追加在后。
+    pub fn render(
+        &self,
+        query: &str,
+        category: &str,
+        language: Option<&str>,
+        instruction: Option<&str>,
+    ) -> String {
+        if let Some(instruction) = instruction {
+            let rendered = fill(instruction, query, category);
+            return if instruction.contains("{query}") {
+                rendered
+            } else {
+                format!("{rendered}\n{query}")
+            };
+        }
+        let languages = language.map(language_keys).unwrap_or_default();
+        let template = languages
+            .iter()
+            .find_map(|language| self.by_category.get(&format!("{category}:{language}")))
+            .or_else(|| self.by_category.get(category))
+            .or_else(|| {
+                languages
+                    .iter()
+                    .find_map(|language| self.by_language.get(language))
+            })
+            .unwrap_or(&self.default);
+        fill(template, query, category)
+    }
+}
+
+/// 语言代码的查找键：先完整代码，再主语言，均为小写
+fn language_keys(language: &str) -> Vec<String> {
+    let language = language.to_ascii_lowercase();
+    match language.split_once('-') {
+        Some((primary, _)) => vec![language.clone(), primary.to_string()],
+        None => vec![language],
+    }
+}
+
+fn fill(template: &str, query: &str, category: &str) -> String {
+    let mut rendered = template.replace("{category}", category);
+    if rendered.contains("{date}") {
+        rendered = rendered.replace("{date}", &today());
+    }
+    // 最后替换 query，避免搜索词中的花括号被当作占位符
+    rendered.replace("{query}", query)
+}
+
+/// 当前 UTC 日期，格式 `YYYY-MM-DD`
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or_default() as i64;
+    civil_date(days)
+}
+
+/// 由 Unix 纪元天数换算公历日期（Howard Hinnant 的 civil_from_days 算法）
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templates() -> QueryTemplates {
+        let mut templates = QueryTemplates::default();
+        templates
+            .by_category
+            .insert("news".to_string(), "news: {query}".to_string());
+        templates
+            .by_language
+            .insert("zh-tw".to_string(), "繁體：{query}".to_string());
+        templates
+    }
+
+    #[test]
+    fn selects_template_by_precedence() {
+        let templates = templates();
+        assert_eq!(
+            templates.render("rust", "news", Some("en"), None),
+            "news: rust"
+        );
+        assert_eq!(
+            templates.render("rust", "it", Some("zh-TW"), None),
+            "繁體：rust"
+        );
+        assert_eq!(
+            templates.render("rust", "it", Some("en-US"), None),
+            "A user is searching the web in the it category. Search target: \"rust\""
+        );
+        assert_eq!(
+            templates.render("rust", "it", Some("de"), None),
+            "用户使用搜索引擎搜索，正在进行it的类型的搜索，搜索目标是\"rust\""
+        );
+    }
+
+    #[test]
+    fn combines_category_with_language() {
+        let mut templates = templates();
+        templates
+            .by_category
+            .insert("it".to_string(), "Technical docs: {query}".to_string());
+        templates
+            .by_category
+            .insert("it:zh".to_string(), "技术文档：{query}".to_string());
+        templates
+            .by_category
+            .insert("it:zh-tw".to_string(), "技術文件：{query}".to_string());
+
+        assert_eq!(
+            templates.render("rust", "it", Some("zh-CN"), None),
+            "技术文档：rust"
+        );
+        assert_eq!(
+            templates.render("rust", "it", Some("zh-TW"), None),
+            "技術文件：rust"
+        );
+        assert_eq!(
+            templates.render("rust", "it", Some("en"), None),
+            "Technical docs: rust"
+        );
+        assert_eq!(
+            templates.render("rust", "it", None, None),
+            "Technical docs: rust"
+        );
+        // 没有对应分类模板时仍使用语言模板
+        assert_eq!(
+            templates.render("rust", "science", Some("zh-TW"), None),
+            "繁體：rust"
+        );
+    }
+
+    #[test]
+    fn instruction_overrides_templates() {
+        let templates = templates();
+        assert_eq!(
+            templates.render("rust", "news", None, Some("find docs for {query}")),
+            "find docs for rust"
+        );
+        assert_eq!(
+            templates.render("rust", "news", None, Some("prefer {category} sites")),
+            "prefer news sites\nrust"
+        );
+    }
+
+    #[test]
+    fn query_braces_are_not_placeholders() {
+        assert_eq!(
+            fill("{query} in {category}", "{category} {date}", "it"),
+            "{category} {date} in it"
+        );
+    }
+
+    #[test]
+    fn converts_epoch_days_to_dates() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(-1), "1969-12-31");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(11_017), "2000-03-01");
+        assert_eq!(civil_date(19_782), "2024-02-29");
+        assert_eq!(civil_date(20_088), "2024-12-31");
+    }
+}
@@ -1,15 +1,32 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
+/// 单次重排序调用的可选参数
+#[derive(Debug, Clone, Default)]
+pub struct RerankOptions {
+    /// 只返回得分最高的前 N 条；支持的提供方在服务端截断，其余在本地截断
+    pub top_n: Option<usize>,
+    /// 在结果中附带文档原文
+    pub return_documents: bool,
+}
+
+/// SiliconFlow、Cohere 与 Jina 共用的请求格式
 #[derive(Debug, Clone, Serialize)]
 pub struct RerankRequest {
     pub model: String,
     pub query: String,
     pub documents: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_n: Option<usize>,
+    /// Cohere v2 不支持该参数，需要时由本地补齐
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_documents: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct RerankResponse {
-    pub id: String,
+    /// Jina 的响应没有 id
+    #[serde(default)]
+    pub id: Option<String>,
     pub results: Vec<RerankResult>,
     #[serde(default)]
     pub meta: Option<serde_json::Value>,
@@ -19,4 +36,45 @@ pub struct RerankResponse {
 pub struct RerankResult {
     pub index: usize,
     pub relevance_score: f64,
+    /// 文档原文，仅在请求 `return_documents` 时返回
+    #[serde(default, deserialize_with = "document_text")]
+    pub document: Option<String>,
+}
+
+/// 提供方返回的文档为 `{"text": "..."}` 对象，部分兼容实现直接返回字符串
+fn document_text<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Document {
+        Text(String),
+        Object { text: String },
+    }
+
+    Ok(
+        Option::<Document>::deserialize(deserializer)?.map(|document| match document {
+            Document::Text(text) | Document::Object { text } => text,
+        }),
+    )
+}
+
+/// TEI `/rerank` 请求
+#[derive(Debug, Clone, Serialize)]
+pub struct TeiRerankRequest {
+    pub query: String,
+    pub texts: Vec<String>,
+    pub truncate: bool,
+    /// 返回未经 sigmoid 的原始得分，由 [`BatchedReranker`](super::batch::BatchedReranker) 统一映射
+    pub raw_scores: bool,
+    pub return_text: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeiRerankResult {
+    pub index: usize,
+    pub score: f64,
+    #[serde(default)]
+    pub text: Option<String>,
 }
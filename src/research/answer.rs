@@ -0,0 +1,342 @@
+use anyhow::Result;
+use tracing::warn;
+
+use super::types::{AnswerMode, AnswerResponse, AnswerSource};
+use crate::{
+    llm::{ChatMessage, LlmClient},
+    searxng::{client::SearxngClient, types::SearchOptions},
+};
+
+const SYSTEM_PROMPT: &str = "You are a research assistant. Answer the user's question using only the numbered sources provided. \
+Cite every claim with the source number in square brackets, e.g. [1] or [2][3]. \
+If the sources do not contain the answer, say so plainly instead of guessing. \
+Answer in the same language as the question.";
+/// 搜索的候选结果条数，由重排序从中挑选作为证据的来源
+const SOURCE_CANDIDATES: usize = 20;
+
+/// 搜索 → 重排序 → 以编号来源为证据调用 LLM 生成带引用的答案
+///
+/// 未配置 LLM 或调用失败时只返回证据，由调用方自行归纳。
+#[derive(Clone)]
+pub struct Answerer {
+    client: SearxngClient,
+    llm: Option<LlmClient>,
+}
+
+impl Answerer {
+    pub fn new(client: SearxngClient, llm: Option<LlmClient>) -> Self {
+        Self { client, llm }
+    }
+
+    pub async fn answer(
+        &self,
+        question: &str,
+        category: Option<&str>,
+        max_sources: usize,
+    ) -> Result<AnswerResponse> {
+        // 候选多于来源数，由重排序挑选证据，而不是直接使用 searXNG 的前 N 条
+        let options = SearchOptions {
+            limit: SOURCE_CANDIDATES.max(max_sources),
+            top_n: Some(max_sources),
+            ..SearchOptions::default()
+        };
+        let search = self.client.search(question, category, &options).await?;
+        let sources: Vec<AnswerSource> = search
+            .results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| AnswerSource {
+                index: index + 1,
+                url: result.url,
+                title: result.title,
+                published_date: result.published_date,
+                snippet: result.description,
+            })
+            .collect();
+
+        let mut response = AnswerResponse {
+            success: true,
+            question: question.to_string(),
+            mode: AnswerMode::EvidenceOnly,
+            answer: None,
+            model: None,
+            citations: Vec::new(),
+            sources,
+            rerank: search.rerank,
+            error: None,
+        };
+
+        let Some(llm) = &self.llm else {
+            return Ok(response);
+        };
+        if response.sources.is_empty() {
+            response.error = Some("no search results to answer from".to_string());
+            return Ok(response);
+        }
+
+        let messages = vec![
+            ChatMessage::system(SYSTEM_PROMPT),
+            ChatMessage::user(build_prompt(question, &response.sources)),
+        ];
+        match llm.complete(messages).await {
+            Ok(completion) => {
+                response.citations = parse_citations(&completion.content, response.sources.len());
+                response.mode = AnswerMode::Synthesized;
+                response.answer = Some(completion.content);
+                response.model = Some(completion.model);
+            }
+            Err(err) => {
+                warn!(error = %err, "Answer synthesis failed, returning evidence only");
+                response.mode = AnswerMode::Fallback;
+                response.error = Some(format!("answer synthesis failed: {err:#}"));
+            }
+        }
+        Ok(response)
+    }
+}
+
+fn build_prompt(question: &str, sources: &[AnswerSource]) -> String {
+    let mut prompt = String::from("Sources:\n");
+    for source in sources {
+        prompt.push_str(&format!("[{}] {}\n", source.index, source.url));
+        if let Some(title) = &source.title {
+            prompt.push_str(&format!("Title: {title}\n"));
+        }
+        if let Some(published_date) = &source.published_date {
+            prompt.push_str(&format!("Published: {published_date}\n"));
+        }
+        prompt.push_str(&source.snippet);
+        prompt.push_str("\n\n");
+    }
+    prompt.push_str("Question: ");
+    prompt.push_str(question);
+    prompt
+}
+
+/// 提取答案中的 `[n]` 引用编号，忽略超出来源范围的编号
+fn parse_citations(answer: &str, sources_len: usize) -> Vec<usize> {
+    let mut citations = Vec::new();
+    let mut rest = answer;
+    while let Some(start) = rest.find('[') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+        // 同时兼容 `[1, 2]` 形式
+        for part in rest[..end].split(',') {
+            if let Ok(index) = part.trim().parse::<usize>()
+                && (1..=sources_len).contains(&index)
+                && !citations.contains(&index)
+            {
+                citations.push(index);
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    citations
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        Json, Router,
+        extract::Query,
+        http::StatusCode,
+        routing::{get, post},
+    };
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{http::HttpConfig, llm::LlmConfig, rerank::Bm25Reranker};
+
+    async fn spawn(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    /// 第 1 页返回 12 条结果，只有第 11 条与 "tokio" 相关
+    async fn spawn_searxng() -> String {
+        async fn search(Query(params): Query<HashMap<String, String>>) -> Json<Value> {
+            let results: Vec<Value> = if params["pageno"] == "1" {
+                (0..12)
+                    .map(|index| {
+                        let title = if index == 10 {
+                            "tokio runtime guide".to_string()
+                        } else {
+                            format!("result {index}")
+                        };
+                        json!({
+                            "url": format!("https://example.com/{index}"),
+                            "title": title,
+                            "content": format!("snippet {index}"),
+                        })
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Json(json!({ "results": results }))
+        }
+        spawn(Router::new().route("/search", get(search))).await
+    }
+
+    /// 以固定状态码与响应体应答 `/chat/completions`，并记录收到的请求
+    async fn spawn_llm(status: StatusCode, body: Value) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let router = Router::new().route(
+            "/chat/completions",
+            post(move |Json(request): Json<Value>| {
+                recorded.lock().unwrap().push(request);
+                let body = body.clone();
+                async move { (status, Json(body)) }
+            }),
+        );
+        (spawn(router).await, requests)
+    }
+
+    async fn answerer(llm_base_url: Option<String>) -> Answerer {
+        let client =
+            SearxngClient::new_with_rerank(spawn_searxng().await, Arc::new(Bm25Reranker::new()));
+        let llm = llm_base_url.map(|base_url| {
+            LlmClient::from_config(LlmConfig {
+                base_url,
+                api_key: None,
+                model: "mock-model".to_string(),
+                http: HttpConfig {
+                    max_retries: 0,
+                    ..HttpConfig::default()
+                },
+                max_tokens: None,
+            })
+            .unwrap()
+        });
+        Answerer::new(client, llm)
+    }
+
+    fn completion(content: &str) -> Value {
+        json!({
+            "model": "mock-model-2024",
+            "choices": [{ "message": { "role": "assistant", "content": content } }],
+        })
+    }
+
+    #[tokio::test]
+    async fn synthesizes_answer_with_citations() {
+        let (base_url, requests) = spawn_llm(
+            StatusCode::OK,
+            completion("Tokio is a runtime [1], see also [2][9]."),
+        )
+        .await;
+        let response = answerer(Some(base_url))
+            .await
+            .answer("tokio", None, 3)
+            .await
+            .unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.mode, AnswerMode::Synthesized);
+        assert_eq!(response.model.as_deref(), Some("mock-model-2024"));
+        assert_eq!(
+            response.answer.as_deref(),
+            Some("Tokio is a runtime [1], see also [2][9].")
+        );
+        assert_eq!(response.citations, [1, 2]);
+        assert_eq!(response.sources.len(), 3);
+        assert_eq!(response.sources[0].index, 1);
+        assert_eq!(response.sources[0].url, "https://example.com/10");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["model"], "mock-model");
+        let prompt = requests[0]["messages"][1]["content"].as_str().unwrap();
+        assert!(prompt.contains("[1] https://example.com/10\nTitle: tokio runtime guide"));
+        assert!(prompt.ends_with("Question: tokio"));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_evidence_on_server_error() {
+        let (base_url, _) =
+            spawn_llm(StatusCode::BAD_GATEWAY, json!({ "error": "unavailable" })).await;
+        let response = answerer(Some(base_url))
+            .await
+            .answer("tokio", None, 3)
+            .await
+            .unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.mode, AnswerMode::Fallback);
+        assert!(response.answer.is_none());
+        assert!(response.citations.is_empty());
+        assert_eq!(response.sources.len(), 3);
+        assert!(
+            response
+                .error
+                .unwrap()
+                .starts_with("answer synthesis failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_evidence_on_empty_content() {
+        let (base_url, _) = spawn_llm(StatusCode::OK, completion("  ")).await;
+        let response = answerer(Some(base_url))
+            .await
+            .answer("tokio", None, 3)
+            .await
+            .unwrap();
+
+        assert_eq!(response.mode, AnswerMode::Fallback);
+        assert!(response.answer.is_none());
+        assert!(response.error.unwrap().contains("no content"));
+    }
+
+    #[tokio::test]
+    async fn returns_evidence_only_without_llm() {
+        let response = answerer(None).await.answer("tokio", None, 2).await.unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.mode, AnswerMode::EvidenceOnly);
+        assert!(response.answer.is_none());
+        assert!(response.error.is_none());
+        // 来源由重排序从 searXNG 的前 max_sources 条之外挑出
+        let urls: Vec<&str> = response
+            .sources
+            .iter()
+            .map(|source| source.url.as_str())
+            .collect();
+        assert_eq!(urls[0], "https://example.com/10");
+        assert_eq!(urls.len(), 2);
+    }
+
+    #[test]
+    fn parses_citations_in_order_of_appearance() {
+        assert_eq!(
+            parse_citations("Rust is fast [2]. It is safe [1][2].", 3),
+            [2, 1]
+        );
+    }
+
+    #[test]
+    fn parses_comma_separated_citations() {
+        assert_eq!(parse_citations("See [1, 3] and [ 2 ].", 3), [1, 3, 2]);
+    }
+
+    #[test]
+    fn ignores_out_of_range_and_non_numeric_brackets() {
+        assert_eq!(parse_citations("[0] [4] [a] [1] [link](x)", 3), [1]);
+        assert!(parse_citations("no citations", 3).is_empty());
+        assert!(parse_citations("[1]", 0).is_empty());
+    }
+
+    #[test]
+    fn handles_unclosed_brackets() {
+        assert_eq!(parse_citations("[2] trailing [1", 3), [2]);
+    }
+}
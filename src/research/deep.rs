@@ -0,0 +1,193 @@
+use anyhow::Result;
+use futures::future::join_all;
+use tracing::{debug, warn};
+
+use super::types::{DeepSearchPage, DeepSearchResponse, Passage, PassageScoring};
+use crate::{
+    fetch::PageFetcher,
+    rerank::{RerankOptions, bm25::bm25_scores},
+    searxng::{client::SearxngClient, types::SearchOptions},
+};
+
+/// 段落切分的目标长度（字符数）
+const PASSAGE_TARGET_CHARS: usize = 600;
+/// 每个页面最多参与打分的段落数，控制重排序调用的文档规模
+const MAX_SCORED_PASSAGES_PER_PAGE: usize = 40;
+/// 搜索的候选结果条数，由重排序从中挑选需要抓取的页面
+const SEARCH_CANDIDATES: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct DeepSearchOptions {
+    /// 抓取并阅读的搜索结果页面数
+    pub pages: usize,
+    /// 每个页面返回的段落数
+    pub passages_per_page: usize,
+}
+
+/// 搜索 → 重排序 → 并行抓取前 N 个页面 → 段落切分与再打分
+///
+/// 段落打分复用搜索客户端上配置的重排序实现。
+#[derive(Clone)]
+pub struct DeepSearcher {
+    client: SearxngClient,
+    fetcher: PageFetcher,
+}
+
+impl DeepSearcher {
+    pub fn new(client: SearxngClient, fetcher: PageFetcher) -> Self {
+        Self { client, fetcher }
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        category: Option<&str>,
+        options: &DeepSearchOptions,
+    ) -> Result<DeepSearchResponse> {
+        // 候选多于页面数，由重排序决定抓取哪些页面，而不是直接使用 searXNG 的前 N 条
+        let search_options = SearchOptions {
+            limit: SEARCH_CANDIDATES.max(options.pages),
+            top_n: Some(options.pages),
+            ..SearchOptions::default()
+        };
+        let search = self.client.search(query, category, &search_options).await?;
+
+        let fetches = search.results.iter().map(|result| async move {
+            let fetched = self.fetcher.fetch(&result.url).await;
+            (result, fetched)
+        });
+        let fetched = join_all(fetches).await;
+
+        let mut pages = Vec::with_capacity(fetched.len());
+        let mut passages: Vec<(usize, String)> = Vec::new();
+        for (rank, (result, fetched)) in fetched.into_iter().enumerate() {
+            let mut page = DeepSearchPage {
+                rank: rank + 1,
+                url: result.url.clone(),
+                title: result.title.clone(),
+                published_at: result.published_date.clone(),
+                success: false,
+                passages: Vec::new(),
+                error: None,
+            };
+            match fetched {
+                Ok(fetched) => {
+                    page.success = true;
+                    // 页面元数据缺失时保留搜索结果中的标题与发布时间
+                    page.title = fetched.title.or(page.title);
+                    page.published_at = fetched.published_at.or(page.published_at);
+                    let mut page_passages = split_passages(&fetched.markdown);
+                    if page_passages.is_empty() {
+                        // 正文为空时退回搜索结果摘要
+                        page_passages.push(result.description.clone());
+                    }
+                    page_passages.truncate(MAX_SCORED_PASSAGES_PER_PAGE);
+                    passages.extend(page_passages.into_iter().map(|text| (rank, text)));
+                }
+                Err(err) => {
+                    warn!(url = %result.url, error = %err, "Fetch page for deep search failed");
+                    page.error = Some(format!("{err:#}"));
+                }
+            }
+            pages.push(page);
+        }
+
+        let (scores, passage_scoring) = self.score_passages(query, &passages).await;
+        for ((rank, text), score) in passages.into_iter().zip(scores) {
+            pages[rank].passages.push(Passage { text, score });
+        }
+        for page in &mut pages {
+            page.passages.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            page.passages.truncate(options.passages_per_page);
+        }
+
+        let success = pages.iter().any(|page| page.success);
+        let error = if success || pages.is_empty() {
+            None
+        } else {
+            Some("all result pages failed to fetch".to_string())
+        };
+        Ok(DeepSearchResponse {
+            success: success || pages.is_empty(),
+            query: query.to_string(),
+            search_type: search.category,
+            rerank: search.rerank,
+            passage_scoring,
+            pages,
+            error,
+        })
+    }
+
+    /// 对所有页面的段落统一打分，重排序不可用或失败时以全部段落为语料按 BM25 打分
+    async fn score_passages(
+        &self,
+        query: &str,
+        passages: &[(usize, String)],
+    ) -> (Vec<f64>, PassageScoring) {
+        if let Some(reranker) = self.client.reranker()
+            && !passages.is_empty()
+        {
+            let documents = passages.iter().map(|(_, text)| text.clone()).collect();
+            match reranker
+                .rerank(query, documents, &RerankOptions::default())
+                .await
+            {
+                Ok(results) => {
+                    let mut scores = vec![0.0; passages.len()];
+                    for result in results {
+                        if let Some(score) = scores.get_mut(result.index) {
+                            *score = result.relevance_score;
+                        }
+                    }
+                    return (scores, PassageScoring::Reranker);
+                }
+                Err(err) => warn!(error = %err, "Rerank passages failed, using lexical scoring"),
+            }
+        }
+
+        debug!(passages = passages.len(), "Scoring passages lexically");
+        let texts: Vec<String> = passages.iter().map(|(_, text)| text.clone()).collect();
+        (bm25_scores(query, &texts), PassageScoring::Lexical)
+    }
+}
+
+/// 按段落切分 Markdown，合并过短的段落、拆分过长的段落
+fn split_passages(markdown: &str) -> Vec<String> {
+    let mut passages = Vec::new();
+    let mut current = String::new();
+
+    for block in markdown.split("\n\n").map(str::trim) {
+        // 跳过空段落与纯图片段落
+        if block.is_empty() || (block.starts_with("![") && !block.contains('\n')) {
+            continue;
+        }
+        let block_chars = block.chars().count();
+        if block_chars > PASSAGE_TARGET_CHARS * 2 {
+            if !current.is_empty() {
+                passages.push(std::mem::take(&mut current));
+            }
+            let chars: Vec<char> = block.chars().collect();
+            passages.extend(
+                chars
+                    .chunks(PASSAGE_TARGET_CHARS)
+                    .map(|chunk| chunk.iter().collect::<String>()),
+            );
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + block_chars > PASSAGE_TARGET_CHARS {
+            passages.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(block);
+    }
+    if !current.is_empty() {
+        passages.push(current);
+    }
+    passages
+}
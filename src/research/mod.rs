@@ -0,0 +1,6 @@
+pub mod answer;
+pub mod deep;
+pub mod types;
+
+pub use answer::Answerer;
+pub use deep::{DeepSearchOptions, DeepSearcher};
@@ -0,0 +1,85 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::searxng::types::RerankStatus;
+
+/// 段落相关性的打分方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PassageScoring {
+    /// 由重排序模型打分
+    Reranker,
+    /// 重排序不可用时按 BM25 词法打分
+    Lexical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Passage {
+    pub text: String,
+    /// 与查询的相关性得分，越高越相关
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeepSearchPage {
+    /// 在搜索结果中的排名（从 1 开始）
+    pub rank: usize,
+    pub url: String,
+    pub title: Option<String>,
+    pub published_at: Option<String>,
+    pub success: bool,
+    /// 按相关性从高到低排列的段落
+    pub passages: Vec<Passage>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeepSearchResponse {
+    pub success: bool,
+    pub query: String,
+    pub search_type: String,
+    /// 搜索结果的重排序情况
+    pub rerank: RerankStatus,
+    pub passage_scoring: PassageScoring,
+    pub pages: Vec<DeepSearchPage>,
+    pub error: Option<String>,
+}
+
+/// answer 工具的生成方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerMode {
+    /// 已由 LLM 基于证据生成带引用的答案
+    Synthesized,
+    /// 未配置 LLM，仅返回证据
+    EvidenceOnly,
+    /// LLM 调用失败，回退为仅返回证据
+    Fallback,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerSource {
+    /// 引用编号，与答案中的 `[n]` 对应（从 1 开始）
+    pub index: usize,
+    pub url: String,
+    pub title: Option<String>,
+    pub published_date: Option<String>,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnswerResponse {
+    pub success: bool,
+    pub question: String,
+    pub mode: AnswerMode,
+    /// 带 `[n]` 引用标记的答案，仅在 mode 为 synthesized 时存在
+    pub answer: Option<String>,
+    /// 生成答案的模型
+    pub model: Option<String>,
+    /// 答案中实际引用到的来源编号，按首次出现顺序排列
+    pub citations: Vec<usize>,
+    pub sources: Vec<AnswerSource>,
+    /// 证据检索的重排序情况
+    pub rerank: RerankStatus,
+    pub error: Option<String>,
+}
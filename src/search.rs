@@ -1,11 +1,22 @@
-use reqwest::Client;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::searxng::{
+    client::SearxngClient,
+    types::{SearchOptions, SearchResult},
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ImageSearchItem {
     pub image_url: String,
     pub description: String,
+    pub thumbnail_url: Option<String>,
+    /// 图片所在的来源页面
+    pub source_url: Option<String>,
+    /// 图片分辨率，如 `1920 x 1080`
+    pub resolution: Option<String>,
+    /// 图片格式，如 `jpeg`
+    pub format: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -23,30 +34,11 @@ pub struct ImageSearchResponse {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct SearxResponse {
-    #[serde(default)]
-    results: Vec<SearxImageResult>,
-}
-
-#[derive(Debug, Deserialize)]
-struct SearxImageResult {
-    title: Option<String>,
-    img_src: Option<String>,
-}
-
-pub async fn search_images(keywords: &[String], limit: usize) -> ImageSearchResponse {
-    let base_url = match resolve_searxng_url() {
-        Ok(url) => url,
-        Err(err) => {
-            return ImageSearchResponse {
-                success: false,
-                results: Vec::new(),
-                error: Some(err),
-            };
-        }
-    };
-
+pub async fn search_images(
+    client: &SearxngClient,
+    keywords: &[String],
+    limit: usize,
+) -> ImageSearchResponse {
     let filtered: Vec<String> = keywords
         .iter()
         .map(|value| value.trim().to_string())
@@ -60,19 +52,11 @@ pub async fn search_images(keywords: &[String], limit: usize) -> ImageSearchResp
         };
     }
 
-    let client = Client::new();
     let mut join_set = tokio::task::JoinSet::new();
 
     for (index, keyword) in filtered.into_iter().enumerate() {
         let client = client.clone();
-        let base_url = base_url.clone();
-        let limit = limit;
-        join_set.spawn(async move {
-            (
-                index,
-                search_single(&client, &base_url, &keyword, limit).await,
-            )
-        });
+        join_set.spawn(async move { (index, search_single(&client, &keyword, limit).await) });
     }
 
     let mut results = Vec::new();
@@ -81,10 +65,10 @@ pub async fn search_images(keywords: &[String], limit: usize) -> ImageSearchResp
     while let Some(task) = join_set.join_next().await {
         match task {
             Ok((index, result)) => {
-                if !result.success {
-                    if let Some(error) = result.error.clone() {
-                        errors.push(format!("{}: {}", result.query, error));
-                    }
+                if !result.success
+                    && let Some(error) = result.error.clone()
+                {
+                    errors.push(format!("{}: {}", result.query, error));
                 }
                 results.push((index, result));
             }
@@ -106,95 +90,119 @@ pub async fn search_images(keywords: &[String], limit: usize) -> ImageSearchResp
     }
 }
 
-async fn search_single(
-    client: &Client,
-    base_url: &str,
-    keyword: &str,
-    limit: usize,
-) -> ImageSearchResult {
-    let url = format!("{}/search", base_url.trim_end_matches('/'));
-    let response = match client
-        .get(url)
-        .query(&[("q", keyword), ("categories", "images"), ("format", "json")])
-        .send()
-        .await
-    {
-        Ok(response) => response,
-        Err(err) => {
-            return ImageSearchResult {
-                query: keyword.to_string(),
-                success: false,
-                images: Vec::new(),
-                error: Some(format!("request failed: {err}")),
-            };
-        }
+/// 按 `images` 类别搜索单个关键词，与 opensearch 共用翻页、缓存、无响应引擎重试与重排序流程
+async fn search_single(client: &SearxngClient, keyword: &str, limit: usize) -> ImageSearchResult {
+    let options = SearchOptions {
+        limit,
+        ..SearchOptions::default()
     };
-
-    if !response.status().is_success() {
-        return ImageSearchResult {
+    match client.search(keyword, Some("images"), &options).await {
+        Ok(response) => ImageSearchResult {
+            query: keyword.to_string(),
+            success: true,
+            images: response
+                .results
+                .into_iter()
+                .map(ImageSearchItem::from)
+                .collect(),
+            error: None,
+        },
+        Err(err) => ImageSearchResult {
             query: keyword.to_string(),
             success: false,
             images: Vec::new(),
-            error: Some(format!("request failed with status {}", response.status())),
-        };
+            error: Some(format!("{err:#}")),
+        },
     }
+}
 
-    let payload: SearxResponse = match response.json().await {
-        Ok(payload) => payload,
-        Err(err) => {
-            return ImageSearchResult {
-                query: keyword.to_string(),
-                success: false,
-                images: Vec::new(),
-                error: Some(format!("decode response failed: {err}")),
-            };
+impl From<SearchResult> for ImageSearchItem {
+    fn from(result: SearchResult) -> Self {
+        Self {
+            image_url: result.url,
+            description: result.description,
+            thumbnail_url: result.thumbnail,
+            source_url: result.source_url,
+            resolution: result.resolution,
+            format: result.img_format,
         }
-    };
+    }
+}
 
-    let images = payload
-        .results
-        .into_iter()
-        .filter_map(|item| {
-            let image_url = item.img_src.and_then(|value| {
-                let trimmed = value.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                }
-            });
-            let description = item.title.and_then(|value| {
-                let trimmed = value.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    Some(trimmed.to_string())
-                }
-            });
-            match (image_url, description) {
-                (Some(image_url), Some(description)) => Some(ImageSearchItem {
-                    image_url,
-                    description,
-                }),
-                _ => None,
-            }
-        })
-        .take(limit)
-        .collect();
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{Json, Router, extract::Query, routing::get};
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    /// 每页返回 3 张图片、共 2 页的 searXNG
+    async fn spawn_searxng() -> String {
+        async fn search(Query(params): Query<HashMap<String, String>>) -> Json<Value> {
+            assert_eq!(params["categories"], "images");
+            let pageno: usize = params["pageno"].parse().unwrap();
+            let results: Vec<Value> = if pageno <= 2 {
+                (0..3)
+                    .map(|index| {
+                        json!({
+                            "url": format!("https://example.com/page/{pageno}/{index}"),
+                            "img_src": format!("https://img.example.com/{pageno}/{index}.jpg"),
+                            "thumbnail_src": format!("https://img.example.com/{pageno}/{index}_s.jpg"),
+                            "title": format!("{} {pageno}-{index}", params["q"]),
+                            "resolution": "640 x 480",
+                            "img_format": "jpeg",
+                        })
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Json(json!({ "results": results }))
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, Router::new().route("/search", get(search)))
+                .await
+                .unwrap();
+        });
+        format!("http://{addr}")
+    }
 
-    ImageSearchResult {
-        query: keyword.to_string(),
-        success: true,
-        images,
-        error: None,
+    #[tokio::test]
+    async fn collects_images_across_pages() {
+        let client = SearxngClient::new(spawn_searxng().await);
+        let keywords = ["cat".to_string(), "  ".to_string(), "dog".to_string()];
+
+        let response = search_images(&client, &keywords, 5).await;
+        assert!(response.success);
+        assert_eq!(response.results.len(), 2);
+        assert_eq!(response.results[1].query, "dog");
+
+        let images = &response.results[0].images;
+        assert_eq!(images.len(), 5);
+        assert_eq!(images[0].image_url, "https://img.example.com/1/0.jpg");
+        assert_eq!(images[0].description, "cat 1-0");
+        assert_eq!(
+            images[0].thumbnail_url.as_deref(),
+            Some("https://img.example.com/1/0_s.jpg")
+        );
+        assert_eq!(
+            images[0].source_url.as_deref(),
+            Some("https://example.com/page/1/0")
+        );
+        assert_eq!(images[0].resolution.as_deref(), Some("640 x 480"));
+        assert_eq!(images[0].format.as_deref(), Some("jpeg"));
+        assert_eq!(images[4].image_url, "https://img.example.com/2/1.jpg");
     }
-}
 
-fn resolve_searxng_url() -> Result<String, String> {
-    let value = std::env::var("SEARXNG_URL").unwrap_or_default();
-    let trimmed = value.trim().trim_end_matches('/');
-    if trimmed.is_empty() {
-        return Err("SEARXNG_URL is required".to_string());
+    #[tokio::test]
+    async fn rejects_empty_keywords() {
+        let client = SearxngClient::new("http://127.0.0.1:9".to_string());
+        let response = search_images(&client, &[" ".to_string()], 5).await;
+        assert!(!response.success);
+        assert_eq!(response.error.as_deref(), Some("keywords is empty"));
     }
-    Ok(trimmed.to_string())
 }
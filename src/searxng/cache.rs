@@ -0,0 +1,266 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use lru::LruCache;
+use redb::{Database, ReadableTable, TableDefinition};
+use tracing::{debug, warn};
+
+use super::types::{SearchFilters, SearxngResponse};
+
+const PAGES_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("searxng_pages");
+/// 缓存键格式版本，结构变化时递增以忽略旧的磁盘数据
+const KEY_VERSION: &str = "v1";
+/// 每次写入磁盘时清理过期数据的概率（1/N），避免每次写入都全表扫描
+const DISK_CLEANUP_EVERY: u32 = 64;
+
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+/// 各类别的缓存有效期
+#[derive(Debug, Clone)]
+pub struct CacheTtls {
+    /// 未单独配置的类别使用的有效期
+    pub default: Duration,
+    pub by_category: HashMap<String, Duration>,
+}
+
+impl Default for CacheTtls {
+    /// 新闻时效性强，缓存 5 分钟；学术结果变化慢，缓存 1 天；其他类别缓存 1 小时
+    fn default() -> Self {
+        Self {
+            default: Duration::from_secs(60 * 60),
+            by_category: HashMap::from([
+                ("news".to_string(), Duration::from_secs(5 * 60)),
+                ("science".to_string(), Duration::from_secs(24 * 60 * 60)),
+                ("images".to_string(), Duration::from_secs(6 * 60 * 60)),
+            ]),
+        }
+    }
+}
+
+impl CacheTtls {
+    pub fn for_category(&self, category: &str) -> Duration {
+        self.by_category
+            .get(category)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// 搜索缓存配置
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// 内存 LRU 最多保留的页面数
+    pub capacity: usize,
+    /// 持久化数据库文件路径；为 `None` 时只使用内存缓存
+    pub path: Option<String>,
+    pub ttls: CacheTtls,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            path: None,
+            ttls: CacheTtls::default(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+    /// 过期时间（Unix 秒）
+    expires_at: u64,
+    response: SearxngResponse,
+}
+
+/// searXNG 单页结果缓存：内存 LRU 在前，可选的 redb 持久化层在后
+pub struct SearchCache {
+    memory: Mutex<LruCache<String, CacheEntry>>,
+    disk: Option<Arc<Database>>,
+    ttls: CacheTtls,
+}
+
+impl SearchCache {
+    pub fn from_config(config: CacheConfig) -> Result<Self> {
+        let capacity = NonZeroUsize::new(config.capacity).context("cache capacity must be > 0")?;
+        let disk = match &config.path {
+            Some(path) => Some(Arc::new(open_database(Path::new(path))?)),
+            None => None,
+        };
+        Ok(Self {
+            memory: Mutex::new(LruCache::new(capacity)),
+            disk,
+            ttls: config.ttls,
+        })
+    }
+
+    /// 由查询条件生成缓存键：query 去除多余空白并转小写，引擎列表排序
+    pub fn key(
+        query: &str,
+        category: Option<&str>,
+        pageno: usize,
+        filters: &SearchFilters,
+    ) -> String {
+        let query = query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let mut engines = filters.engines.clone();
+        engines.sort();
+        let mut excluded = filters.excluded_engines.clone();
+        excluded.sort();
+        [
+            KEY_VERSION,
+            &query,
+            category.unwrap_or("general"),
+            filters.language.as_deref().unwrap_or(""),
+            filters.time_range.map(|value| value.as_str()).unwrap_or(""),
+            filters
+                .safesearch
+                .map(|value| value.as_param())
+                .unwrap_or(""),
+            &engines.join(","),
+            &excluded.join(","),
+            &pageno.to_string(),
+        ]
+        .join("\u{1f}")
+    }
+
+    pub async fn get(&self, key: &str) -> Option<SearxngResponse> {
+        let now = unix_now();
+        {
+            let mut memory = self.memory.lock().unwrap();
+            match memory.get(key) {
+                Some(entry) if entry.expires_at > now => return Some(entry.response.clone()),
+                Some(_) => {
+                    memory.pop(key);
+                }
+                None => {}
+            }
+        }
+
+        let disk = self.disk.clone()?;
+        let lookup_key = key.to_string();
+        let entry = tokio::task::spawn_blocking(move || read_entry(&disk, &lookup_key))
+            .await
+            .ok()?
+            .unwrap_or_else(|err| {
+                warn!(error = %err, "Read search cache failed");
+                None
+            })?;
+        if entry.expires_at <= now {
+            return None;
+        }
+        debug!("Search cache hit on disk");
+        let response = entry.response.clone();
+        self.memory.lock().unwrap().put(key.to_string(), entry);
+        Some(response)
+    }
+
+    /// 写入缓存，有效期按类别决定
+    pub async fn put(&self, key: String, category: Option<&str>, response: &SearxngResponse) {
+        let ttl = self.ttls.for_category(category.unwrap_or("general"));
+        let entry = CacheEntry {
+            expires_at: unix_now() + ttl.as_secs(),
+            response: response.clone(),
+        };
+        self.memory.lock().unwrap().put(key.clone(), entry.clone());
+
+        if let Some(disk) = self.disk.clone() {
+            let result =
+                tokio::task::spawn_blocking(move || write_entry(&disk, &key, &entry)).await;
+            match result {
+                Ok(Err(err)) => warn!(error = %err, "Write search cache failed"),
+                Err(err) => warn!(error = %err, "Write search cache task failed"),
+                Ok(Ok(())) => {}
+            }
+        }
+    }
+}
+
+fn open_database(path: &Path) -> Result<Database> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create cache directory {} failed", parent.display()))?;
+    }
+    let database = Database::create(path)
+        .with_context(|| format!("open cache database {} failed", path.display()))?;
+    // 预先建表，避免读事务遇到表不存在
+    let txn = database.begin_write()?;
+    txn.open_table(PAGES_TABLE)?;
+    txn.commit()?;
+    Ok(database)
+}
+
+/// 磁盘值格式：8 字节大端过期时间 + JSON 编码的响应
+fn read_entry(database: &Database, key: &str) -> Result<Option<CacheEntry>> {
+    let txn = database.begin_read()?;
+    let table = txn.open_table(PAGES_TABLE)?;
+    let Some(value) = table.get(key)? else {
+        return Ok(None);
+    };
+    let bytes = value.value();
+    if bytes.len() < 8 {
+        return Ok(None);
+    }
+    let (expires_at, payload) = bytes.split_at(8);
+    let expires_at = u64::from_be_bytes(expires_at.try_into()?);
+    // 无法解析的旧数据视为未命中
+    Ok(serde_json::from_slice(payload)
+        .ok()
+        .map(|response| CacheEntry {
+            expires_at,
+            response,
+        }))
+}
+
+fn write_entry(database: &Database, key: &str, entry: &CacheEntry) -> Result<()> {
+    let mut bytes = entry.expires_at.to_be_bytes().to_vec();
+    serde_json::to_writer(&mut bytes, &entry.response)?;
+    let txn = database.begin_write()?;
+    {
+        let mut table = txn.open_table(PAGES_TABLE)?;
+        table.insert(key, bytes.as_slice())?;
+
+        if fastrand::u32(..DISK_CLEANUP_EVERY) == 0 {
+            let now = unix_now();
+            let expired: Vec<String> = table
+                .iter()?
+                .filter_map(|item| item.ok())
+                .filter(|(_, value)| {
+                    value
+                        .value()
+                        .get(..8)
+                        .and_then(|bytes| bytes.try_into().ok())
+                        .is_none_or(|bytes| u64::from_be_bytes(bytes) <= now)
+                })
+                .map(|(key, _)| key.value().to_string())
+                .collect();
+            debug!(
+                expired = expired.len(),
+                "Cleaning expired search cache entries"
+            );
+            for key in expired {
+                table.remove(key.as_str())?;
+            }
+        }
+    }
+    txn.commit()?;
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
@@ -1,120 +1,1008 @@
+use std::{collections::HashSet, sync::Arc, time::Instant};
+
 use anyhow::{Context, Result};
 use reqwest::Client;
-use tracing::debug;
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::{debug, info, warn};
 
 use super::{
-    mapper::map_result_item,
-    types::{SearchToolResponse, SearxngResponse},
+    cache::SearchCache,
+    diversity::{cap_per_domain, mmr_rerank},
+    fusion::{FusedSearchResult, reciprocal_rank_fusion},
+    mapper::{map_answer, map_infobox, map_result_item},
+    pool::{BalanceStrategy, UpstreamPool},
+    types::{
+        CacheStatus, EngineWarning, QuerySearchResult, RerankStatus, SearchFilters, SearchOptions,
+        SearchResult, SearchToolResponse, SearxngResponse,
+    },
+};
+use crate::{
+    http::{HttpConfig, OutboundClient},
+    rerank::{QueryTemplates, RerankOptions, RerankResult, Reranker},
 };
-use crate::rerank::RerankClient;
+
+/// 单次搜索最多向后翻取的 searXNG 页数，避免 limit 较大时请求过多
+const MAX_PAGES_PER_SEARCH: usize = 5;
+/// 存在无响应引擎且结果少于该数量（不超过 limit）时，排除这些引擎重试一次
+const UNRESPONSIVE_RETRY_THRESHOLD: usize = 5;
+/// 配置了重排序时，收集 limit 的若干倍候选交给重排序挑选
+const RERANK_CANDIDATE_FACTOR: usize = 3;
+/// 重排序候选的最大条数
+const MAX_RERANK_CANDIDATES: usize = 100;
+
+/// 单页 searXNG 原始结果及其是否来自缓存
+pub(crate) struct FetchedPage {
+    pub response: SearxngResponse,
+    pub cached: bool,
+}
+
+/// 收集到的候选结果在 searXNG 分页中的位置，用于计算下一次调用的翻页游标
+struct PageCursor {
+    /// 每条候选的（页码, 页内位置），顺序与候选一致，可能多于返回的候选条数
+    positions: Vec<(usize, usize)>,
+    /// 最后获取的页码
+    last_page: usize,
+    /// searXNG 已没有更多结果
+    exhausted: bool,
+}
+
+impl PageCursor {
+    /// 前 `consumed` 条候选已使用，将游标指向第一条未使用的候选
+    fn advance(&self, consumed: usize, response: &mut SearchToolResponse) {
+        if let Some(&(page, position)) = self.positions.get(consumed) {
+            response.next_page = Some(page);
+            response.next_offset = position;
+        } else {
+            response.next_page = (!self.exhausted).then_some(self.last_page + 1);
+            response.next_offset = 0;
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct SearxngClient {
-    base_url: String,
-    http: Client,
-    rerank_client: Option<RerankClient>,
+    pool: Arc<UpstreamPool>,
+    http: OutboundClient,
+    reranker: Option<Arc<dyn Reranker>>,
+    rerank_fallback: Option<Arc<dyn Reranker>>,
+    cache: Option<Arc<SearchCache>>,
+    query_templates: Arc<QueryTemplates>,
 }
 
 impl SearxngClient {
     pub fn new(base_url: String) -> Self {
-        Self {
-            base_url,
-            http: Client::new(),
-            rerank_client: None,
-        }
+        Self::with_pool(
+            Arc::new(UpstreamPool::new(
+                vec![base_url],
+                BalanceStrategy::RoundRobin,
+            )),
+            None,
+            HttpConfig::default(),
+        )
+        .expect("default searxng http config is valid")
     }
 
     /// 创建带有重排序功能的搜索客户端
-    pub fn new_with_rerank(base_url: String, rerank_client: RerankClient) -> Self {
-        Self {
-            base_url,
-            http: Client::new(),
-            rerank_client: Some(rerank_client),
-        }
+    pub fn new_with_rerank(base_url: String, reranker: Arc<dyn Reranker>) -> Self {
+        Self::with_pool(
+            Arc::new(UpstreamPool::new(
+                vec![base_url],
+                BalanceStrategy::RoundRobin,
+            )),
+            Some(reranker),
+            HttpConfig::default(),
+        )
+        .expect("default searxng http config is valid")
+    }
+
+    /// 使用多个 searXNG 实例创建搜索客户端，请求失败时自动切换到下一个实例
+    pub fn with_pool(
+        pool: Arc<UpstreamPool>,
+        reranker: Option<Arc<dyn Reranker>>,
+        http: HttpConfig,
+    ) -> Result<Self> {
+        Ok(Self {
+            pool,
+            http: OutboundClient::new("searxng", http, Client::builder())?,
+            reranker,
+            rerank_fallback: None,
+            cache: None,
+            query_templates: Arc::new(QueryTemplates::default()),
+        })
+    }
+
+    /// 主重排序失败时改用的重排序实现，通常为本地 BM25
+    pub fn with_rerank_fallback(mut self, fallback: Arc<dyn Reranker>) -> Self {
+        self.rerank_fallback = Some(fallback);
+        self
+    }
+
+    /// 替换重排序 query 模板
+    pub fn with_query_templates(mut self, templates: Arc<QueryTemplates>) -> Self {
+        self.query_templates = templates;
+        self
+    }
+
+    /// 在 searXNG 请求前增加结果缓存
+    pub fn with_cache(mut self, cache: Arc<SearchCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn pool(&self) -> &Arc<UpstreamPool> {
+        &self.pool
+    }
+
+    pub fn http(&self) -> &OutboundClient {
+        &self.http
+    }
+
+    pub fn reranker(&self) -> Option<&Arc<dyn Reranker>> {
+        self.reranker.as_ref()
     }
 
     pub async fn search(
         &self,
         query: &str,
         category: Option<&str>,
+        options: &SearchOptions,
     ) -> Result<SearchToolResponse> {
+        let limit = options.limit.max(1);
+        // top_n 只能进一步减少返回条数
+        let keep = options.top_n.map_or(limit, |top_n| top_n.clamp(1, limit));
+        let first_page = options.page.max(1);
         let mut response = SearchToolResponse {
             query: query.to_string(),
             category: category.unwrap_or("general").to_string(),
             success: false,
             results: Vec::new(),
+            page: first_page,
+            next_page: None,
+            next_offset: 0,
+            rerank: RerankStatus::Disabled,
+            cache: CacheStatus::Disabled,
+            answers: Vec::new(),
+            infoboxes: Vec::new(),
+            suggestions: Vec::new(),
+            corrections: Vec::new(),
+            number_of_results: None,
+            warnings: Vec::new(),
+            excluded_engines: Vec::new(),
             error: None,
         };
 
-        let endpoint = format!("{}/search", self.base_url.trim_end_matches('/'));
-        let mut request = self
-            .http
-            .get(endpoint)
-            .query(&[("q", query), ("format", "json")]);
-
-        if let Some(category) = category {
-            request = request.query(&[("categories", category)]);
-        }
-
-        let payload = request
-            .send()
-            .await
-            .context("request searxng failed")?
-            .error_for_status()
-            .context("searxng returned error status")?
-            .json::<SearxngResponse>()
-            .await
-            .context("decode searxng response failed")?;
-
         let category_key = category.unwrap_or("general");
-        let mut results: Vec<_> = payload
-            .results
-            .into_iter()
-            .filter_map(|item| map_result_item(category_key, item))
+        // 重排序从更大的候选池中挑选，使 searXNG 排名靠后但更相关的结果也能进入返回结果
+        let candidates = if self.reranker.is_some() {
+            (limit * RERANK_CANDIDATE_FACTOR).clamp(limit, MAX_RERANK_CANDIDATES.max(limit))
+        } else {
+            limit
+        };
+        let (mut results, mut cursor) = self
+            .collect_pages(
+                query,
+                category,
+                candidates,
+                options.offset,
+                &options.filters,
+                &mut response,
+            )
+            .await?;
+
+        // 有引擎无响应且结果过少时，排除这些引擎重试一次，结果更多时采用重试结果
+        let mut retry_filters = options.filters.clone();
+        retry_filters.excluded_engines = response
+            .warnings
+            .iter()
+            .map(|warning| warning.engine.clone())
             .collect();
+        // 显式指定的引擎全部无响应时没有可用于重试的引擎
+        let has_remaining_engines = retry_filters.engines.is_empty()
+            || retry_filters
+                .engines
+                .iter()
+                .any(|engine| !retry_filters.excluded_engines.contains(engine));
+        if results.len() < UNRESPONSIVE_RETRY_THRESHOLD.min(limit)
+            && !retry_filters.excluded_engines.is_empty()
+            && has_remaining_engines
+        {
+            info!(
+                query = %query,
+                results = results.len(),
+                excluded = ?retry_filters.excluded_engines,
+                "Retrying search without unresponsive engines"
+            );
+            let mut retry_response = response.clone();
+            match self
+                .collect_pages(
+                    query,
+                    category,
+                    candidates,
+                    options.offset,
+                    &retry_filters,
+                    &mut retry_response,
+                )
+                .await
+            {
+                Ok((retry_results, retry_cursor)) if retry_results.len() > results.len() => {
+                    retry_response.excluded_engines = retry_filters.excluded_engines;
+                    response = retry_response;
+                    results = retry_results;
+                    cursor = retry_cursor;
+                }
+                Ok(_) => debug!("Retry without unresponsive engines returned no more results"),
+                Err(err) => warn!(error = %err, "Retry without unresponsive engines failed"),
+            }
+        }
 
-        // 如果配置了重排序客户端，则对结果进行重排序
-        if let Some(rerank_client) = &self.rerank_client {
+        // 如果配置了重排序，则对结果进行重排序
+        if let Some(reranker) = &self.reranker {
             if results.is_empty() {
                 debug!("Skipping rerank: no search results");
+                response.rerank = RerankStatus::Skipped;
             } else {
-                // 构造待排序文档：将 URL 与 description 组合，给 rerank 更多上下文
-                // 形如："{url} - {description}"
+                // 构造待排序文档：将标题、URL 与 description 组合，给 rerank 更多上下文
+                // 形如："{title} - {url} - {description}"
                 let documents: Vec<String> = results
                     .iter()
-                    .map(|r| format!("{} - {}", r.url, r.description))
+                    .map(|r| match &r.title {
+                        Some(title) => format!("{} - {} - {}", title, r.url, r.description),
+                        None => format!("{} - {}", r.url, r.description),
+                    })
                     .collect();
 
-                // 构造增强 query：注入搜索类型(category) + 用户原始搜索词
-                // 模板：用户使用搜索引擎搜索，正在进行{search_type}的类型的搜索，搜索目标是"{user_query}"
-                let rerank_query = format!(
-                    "用户使用搜索引擎搜索，正在进行{}的类型的搜索，搜索目标是\"{}\"",
-                    category_key, query
-                );
+                // 构造增强 query：按分类与语言选择模板，注入搜索类型(category) + 用户原始搜索词
+                // 词法重排序按词匹配打分，模板中的固定文字只会干扰得分，直接使用原始搜索词
+                let rerank_query = if reranker.uses_query_template() {
+                    self.query_templates.render(
+                        query,
+                        category_key,
+                        options.filters.language.as_deref(),
+                        options.rerank_instruction.as_deref(),
+                    )
+                } else {
+                    query.to_string()
+                };
+
+                // 需要多样性调整时保留全部候选，由后续步骤从中挑选
+                let diversify = options.mmr_lambda.is_some() || options.max_per_domain.is_some();
+                let rerank_options = RerankOptions {
+                    top_n: Some(keep).filter(|&keep| keep < documents.len() && !diversify),
+                    return_documents: false,
+                };
 
                 // 调用重排序 API
-                match rerank_client.rerank(&rerank_query, documents).await {
+                match reranker
+                    .rerank(&rerank_query, documents.clone(), &rerank_options)
+                    .await
+                {
                     Ok(rerank_results) => {
-                        // 根据重排序结果重新排列搜索结果
-                        let mut reordered_results = Vec::new();
-                        for rerank_result in rerank_results {
-                            if let Some(result) = results.get(rerank_result.index) {
-                                reordered_results.push(result.clone());
+                        results = reorder(&results, rerank_results);
+                        response.rerank = RerankStatus::Applied;
+                    }
+                    Err(e) => match &self.rerank_fallback {
+                        // 远程重排序失败时改用本地词法重排序
+                        Some(fallback) => {
+                            warn!(error = %e, "Rerank failed, using lexical fallback");
+                            match fallback.rerank(query, documents, &rerank_options).await {
+                                Ok(rerank_results) => {
+                                    results = reorder(&results, rerank_results);
+                                    response.rerank = RerankStatus::Lexical;
+                                }
+                                Err(err) => {
+                                    warn!(error = %err, "Lexical rerank failed, using original order");
+                                    response.rerank = RerankStatus::Fallback;
+                                }
                             }
                         }
-                        results = reordered_results;
-                    }
-                    Err(e) => {
-                        // 重排序失败时记录错误但不影响搜索结果返回
-                        eprintln!("Rerank failed: {}, using original order", e);
-                    }
+                        None => {
+                            // 重排序失败时记录错误但不影响搜索结果返回
+                            warn!(error = %e, "Rerank failed, using original order");
+                            response.rerank = RerankStatus::Fallback;
+                        }
+                    },
                 }
             }
         }
 
-        // 返回 searXNG 全量结果（如启用 rerank，则返回重排后的全量结果）
+        // 得分阈值只对重排序模型的得分生效：词法回退（lexical）以及以 BM25 作为主重排序时，
+        // 得分按本次最高分归一化，最相关的结果总是 1，阈值没有固定含义，因此不过滤
+        if let Some(min_score) = options.min_score
+            && response.rerank == RerankStatus::Applied
+            && self
+                .reranker
+                .as_ref()
+                .is_some_and(|reranker| reranker.absolute_scores())
+        {
+            let before = results.len();
+            results.retain(|result| {
+                result
+                    .relevance_score
+                    .is_some_and(|score| score >= min_score)
+            });
+            debug!(
+                min_score,
+                dropped = before - results.len(),
+                "Dropped results below min_score"
+            );
+        }
+
+        // 多样性调整在截断之前进行，使返回的条数覆盖更多独立来源
+        if let Some(lambda) = options.mmr_lambda {
+            results = mmr_rerank(results, lambda);
+        }
+        if let Some(max_per_domain) = options.max_per_domain {
+            let before = results.len();
+            results = cap_per_domain(results, max_per_domain.max(1));
+            debug!(
+                max_per_domain,
+                dropped = before - results.len(),
+                "Dropped results over the per-domain cap"
+            );
+        }
+
+        // 重排序已对整个候选池打分，下一页从候选池之后开始；未重排序时只使用了前 keep 条
+        let consumed = match response.rerank {
+            RerankStatus::Applied | RerankStatus::Lexical => candidates,
+            _ => keep,
+        };
+        cursor.advance(consumed, &mut response);
+
+        // 在重排序之后截断，保证返回的是最相关的 limit（或 top_n）条
+        results.truncate(keep);
         response.results = results;
         response.success = true;
         Ok(response)
     }
+
+    /// 从 `response.page` 的第 `offset` 条开始，按 searXNG 顺序收集最多 limit 条候选结果，
+    /// 并填充答案、建议与无响应引擎等附加信息
+    ///
+    /// 单页结果不足 limit 时继续向后翻页，直到凑满 limit、结果耗尽或达到翻页上限。
+    /// 超出 limit 的结果不会丢失：返回的 [`PageCursor`] 记录所有候选的位置，
+    /// 确定实际使用的条数后由它设置 `next_page` 与 `next_offset`。
+    async fn collect_pages(
+        &self,
+        query: &str,
+        category: Option<&str>,
+        limit: usize,
+        offset: usize,
+        filters: &SearchFilters,
+        response: &mut SearchToolResponse,
+    ) -> Result<(Vec<SearchResult>, PageCursor)> {
+        let category_key = category.unwrap_or("general");
+        let first_page = response.page;
+        // (结果, 所在页码, 页内位置)
+        let mut candidates: Vec<(SearchResult, usize, usize)> = Vec::new();
+        let mut seen_urls = HashSet::new();
+        let mut pageno = first_page;
+        let mut exhausted = false;
+        let mut fetched_pages = 0;
+        let mut cached_pages = 0;
+
+        loop {
+            let payload = match self.fetch_page(query, category, pageno, filters).await {
+                Ok(page) => {
+                    fetched_pages += 1;
+                    if page.cached {
+                        cached_pages += 1;
+                    }
+                    page.response
+                }
+                // 首页失败直接返回错误；后续页失败时保留已获取的结果
+                Err(err) if pageno == first_page => return Err(err),
+                Err(err) => {
+                    warn!(error = %err, pageno, "Fetch additional searxng page failed");
+                    break;
+                }
+            };
+
+            for (engine, reason) in payload.unresponsive_engines {
+                if !response
+                    .warnings
+                    .iter()
+                    .any(|warning| warning.engine == engine)
+                {
+                    warn!(engine = %engine, reason = ?reason, pageno, "searxng engine unresponsive");
+                    response.warnings.push(EngineWarning {
+                        engine,
+                        reason: reason.unwrap_or_else(|| "unknown".to_string()),
+                    });
+                }
+            }
+
+            // 答案、知识卡片与建议只取起始页，后续页为重复内容
+            if pageno == first_page {
+                response.answers = payload.answers.into_iter().filter_map(map_answer).collect();
+                response.infoboxes = payload
+                    .infoboxes
+                    .into_iter()
+                    .filter_map(map_infobox)
+                    .collect();
+                response.suggestions = payload.suggestions;
+                response.corrections = payload.corrections;
+                response.number_of_results = payload
+                    .number_of_results
+                    .filter(|count| *count > 0.0)
+                    .map(|count| count as u64);
+            }
+
+            // 页内位置按映射后的结果计算，与跨页去重无关，保证游标在不同调用之间稳定
+            let skip = if pageno == first_page { offset } else { 0 };
+            let page_results: Vec<SearchResult> = payload
+                .results
+                .into_iter()
+                .filter_map(|item| map_result_item(category_key, item))
+                .collect();
+            let page_len = page_results.len();
+            let before = candidates.len();
+            candidates.extend(
+                page_results
+                    .into_iter()
+                    .enumerate()
+                    .skip(skip)
+                    .filter(|(_, result)| seen_urls.insert(result.url.clone()))
+                    .map(|(position, result)| (result, pageno, position)),
+            );
+            debug!(
+                pageno,
+                new_results = candidates.len() - before,
+                total_results = candidates.len(),
+                "Fetched searxng page"
+            );
+
+            // 游标越过起始页末尾时继续下一页，其余情况没有新结果即视为耗尽
+            if candidates.len() == before && (skip == 0 || page_len == 0) {
+                exhausted = true;
+                break;
+            }
+            if candidates.len() >= limit || pageno + 1 - first_page >= MAX_PAGES_PER_SEARCH {
+                break;
+            }
+            pageno += 1;
+        }
+
+        let cursor = PageCursor {
+            positions: candidates
+                .iter()
+                .map(|(_, page, position)| (*page, *position))
+                .collect(),
+            last_page: pageno,
+            exhausted,
+        };
+        let results: Vec<SearchResult> = candidates
+            .into_iter()
+            .take(limit)
+            .map(|(result, _, _)| result)
+            .collect();
+        response.cache = if self.cache.is_none() {
+            CacheStatus::Disabled
+        } else if cached_pages == 0 {
+            CacheStatus::Miss
+        } else if cached_pages == fetched_pages {
+            CacheStatus::Hit
+        } else {
+            CacheStatus::Partial
+        };
+        Ok((results, cursor))
+    }
+
+    /// 并发执行多个 query，最多同时进行 `concurrency` 个请求
+    ///
+    /// 返回结果与输入 `queries` 顺序一致，单个 query 失败不影响其他 query。
+    pub async fn search_many(
+        &self,
+        queries: &[String],
+        category: Option<&str>,
+        options: &SearchOptions,
+        concurrency: usize,
+    ) -> Vec<QuerySearchResult> {
+        let searches: Vec<(String, Option<&str>)> = queries
+            .iter()
+            .map(|query| (query.clone(), category))
+            .collect();
+        self.search_each(searches, options, concurrency).await
+    }
+
+    /// 对每个 query 与类别的组合分别搜索，再按倒数排名融合为一个结果列表
+    ///
+    /// 融合后的结果按规范化 URL 去重，并记录命中的 query 与类别，最多保留 limit（或 top_n）条。
+    /// 返回的各次搜索只保留答案、警告等元信息，结果已并入融合列表。
+    pub async fn search_fused(
+        &self,
+        queries: &[String],
+        categories: &[Option<&str>],
+        options: &SearchOptions,
+        concurrency: usize,
+    ) -> (Vec<QuerySearchResult>, Vec<FusedSearchResult>) {
+        let searches: Vec<(String, Option<&str>)> = categories
+            .iter()
+            .flat_map(|&category| queries.iter().map(move |query| (query.clone(), category)))
+            .collect();
+        let mut results = self.search_each(searches, options, concurrency).await;
+
+        let limit = options.limit.max(1);
+        let keep = options.top_n.map_or(limit, |top_n| top_n.clamp(1, limit));
+        let fused = reciprocal_rank_fusion(&results, keep);
+        debug!(
+            searches = results.len(),
+            fused = fused.len(),
+            "Fused search results with reciprocal rank fusion"
+        );
+        for result in &mut results {
+            result.results.clear();
+        }
+        (results, fused)
+    }
+
+    /// 并发执行多次搜索，返回结果与输入顺序一致
+    async fn search_each(
+        &self,
+        searches: Vec<(String, Option<&str>)>,
+        options: &SearchOptions,
+        concurrency: usize,
+    ) -> Vec<QuerySearchResult> {
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut join_set = JoinSet::new();
+
+        for (index, (query, category)) in searches.iter().cloned().enumerate() {
+            let client = self.clone();
+            let category = category.map(str::to_string);
+            let options = options.clone();
+            let semaphore = semaphore.clone();
+            join_set.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = match client.search(&query, category.as_deref(), &options).await {
+                    Ok(response) => QuerySearchResult::from(response),
+                    Err(err) => QuerySearchResult::failed(
+                        query,
+                        category.unwrap_or_else(|| "general".to_string()),
+                        options.page,
+                        format!("{err:#}"),
+                    ),
+                };
+                (index, result)
+            });
+        }
+
+        let mut slots: Vec<Option<QuerySearchResult>> = vec![None; searches.len()];
+        while let Some(task) = join_set.join_next().await {
+            match task {
+                Ok((index, result)) => slots[index] = Some(result),
+                Err(err) => warn!(error = %err, "search task failed to join"),
+            }
+        }
+
+        // 任务异常退出时补齐失败结果，保证与输入一一对应
+        slots
+            .into_iter()
+            .zip(searches)
+            .map(|(slot, (query, category))| {
+                slot.unwrap_or_else(|| {
+                    QuerySearchResult::failed(
+                        query,
+                        category.unwrap_or("general").to_string(),
+                        options.page,
+                        "search task aborted".to_string(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// 获取 searXNG 的单页原始结果，启用缓存时优先读取缓存
+    ///
+    /// 只缓存所有引擎均有响应且结果非空的页面，避免把上游限流时的残缺结果保留下来。
+    async fn fetch_page(
+        &self,
+        query: &str,
+        category: Option<&str>,
+        pageno: usize,
+        filters: &SearchFilters,
+    ) -> Result<FetchedPage> {
+        let Some(cache) = &self.cache else {
+            let response = self
+                .fetch_upstream(query, category, pageno, filters)
+                .await?;
+            return Ok(FetchedPage {
+                response,
+                cached: false,
+            });
+        };
+
+        let key = SearchCache::key(query, category, pageno, filters);
+        if let Some(response) = cache.get(&key).await {
+            debug!(query = %query, pageno, "Search cache hit");
+            return Ok(FetchedPage {
+                response,
+                cached: true,
+            });
+        }
+
+        let response = self
+            .fetch_upstream(query, category, pageno, filters)
+            .await?;
+        if response.unresponsive_engines.is_empty() && !response.results.is_empty() {
+            cache.put(key, category, &response).await;
+        }
+        Ok(FetchedPage {
+            response,
+            cached: false,
+        })
+    }
+
+    /// 请求 searXNG 的单页原始结果
+    ///
+    /// 按实例池给出的顺序依次尝试，某个实例失败时切换到下一个实例。
+    async fn fetch_upstream(
+        &self,
+        query: &str,
+        category: Option<&str>,
+        pageno: usize,
+        filters: &SearchFilters,
+    ) -> Result<SearxngResponse> {
+        let mut last_error = None;
+        for index in self.pool.candidates() {
+            let base_url = self.pool.base_url(index);
+            let started = Instant::now();
+            match self
+                .fetch_page_from(base_url, query, category, pageno, filters)
+                .await
+            {
+                Ok(payload) => {
+                    self.pool.record_success(index, started.elapsed());
+                    return Ok(payload);
+                }
+                Err(err) => {
+                    let timeout = err.chain().any(|cause| {
+                        cause
+                            .downcast_ref::<reqwest::Error>()
+                            .is_some_and(reqwest::Error::is_timeout)
+                    });
+                    self.pool.record_failure(index, timeout);
+                    if self.pool.len() > 1 {
+                        warn!(upstream = %base_url, error = %err, "searxng upstream failed, trying next");
+                    }
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        match last_error {
+            Some(err) if self.pool.len() > 1 => {
+                Err(err.context(format!("all {} searxng upstreams failed", self.pool.len())))
+            }
+            Some(err) => Err(err),
+            None => anyhow::bail!("no searxng upstream configured"),
+        }
+    }
+
+    async fn fetch_page_from(
+        &self,
+        base_url: &str,
+        query: &str,
+        category: Option<&str>,
+        pageno: usize,
+        filters: &SearchFilters,
+    ) -> Result<SearxngResponse> {
+        let endpoint = format!("{base_url}/search");
+        let pageno = pageno.to_string();
+        let mut request = self.http.inner().get(endpoint).query(&[
+            ("q", query),
+            ("format", "json"),
+            ("pageno", pageno.as_str()),
+        ]);
+
+        if let Some(category) = category {
+            request = request.query(&[("categories", category)]);
+        }
+        if let Some(time_range) = filters.time_range {
+            request = request.query(&[("time_range", time_range.as_str())]);
+        }
+        if let Some(language) = &filters.language {
+            request = request.query(&[("language", language.as_str())]);
+        }
+        if let Some(safesearch) = filters.safesearch {
+            request = request.query(&[("safesearch", safesearch.as_param())]);
+        }
+        if !filters.engines.is_empty() {
+            let engines: Vec<&str> = filters
+                .engines
+                .iter()
+                .filter(|engine| !filters.excluded_engines.contains(engine))
+                .map(String::as_str)
+                .collect();
+            request = request.query(&[("engines", engines.join(","))]);
+        } else if !filters.excluded_engines.is_empty() {
+            // searXNG 偏好设置格式：`{引擎名}__{类别}`，逗号分隔
+            let category = category.unwrap_or("general");
+            let disabled: Vec<String> = filters
+                .excluded_engines
+                .iter()
+                .map(|engine| format!("{engine}__{category}"))
+                .collect();
+            request = request.query(&[("disabled_engines", disabled.join(","))]);
+        }
+
+        self.http
+            .send(request)
+            .await
+            .context("request searxng failed")?
+            .error_for_status()
+            .context("searxng returned error status")?
+            .json::<SearxngResponse>()
+            .await
+            .context("decode searxng response failed")
+    }
+}
+
+/// 按重排序结果的顺序重新排列搜索结果并记录得分，未返回得分的结果被丢弃
+fn reorder(results: &[SearchResult], rerank_results: Vec<RerankResult>) -> Vec<SearchResult> {
+    rerank_results
+        .into_iter()
+        .filter_map(|rerank_result| {
+            let mut result = results.get(rerank_result.index)?.clone();
+            result.relevance_score = Some(rerank_result.relevance_score);
+            Some(result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{Json, Router, extract::Query, http::StatusCode, routing::get};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::rerank::Bm25Reranker;
+
+    /// 启动模拟的 searXNG 实例，`respond` 根据请求参数返回状态码与 JSON 响应
+    async fn spawn_searxng<F>(respond: F) -> String
+    where
+        F: Fn(&HashMap<String, String>) -> (StatusCode, Value) + Clone + Send + Sync + 'static,
+    {
+        let app = Router::new().route(
+            "/search",
+            get(move |Query(params): Query<HashMap<String, String>>| {
+                let respond = respond.clone();
+                async move {
+                    let (status, body) = respond(&params);
+                    (status, Json(body))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        base_url
+    }
+
+    /// 前两页每页 20 条结果，之后为空
+    fn paged_results(params: &HashMap<String, String>) -> (StatusCode, Value) {
+        let pageno: usize = params["pageno"].parse().unwrap();
+        let results: Vec<Value> = if pageno <= 2 {
+            (0..20)
+                .map(|index| {
+                    json!({
+                        "url": format!("https://example.com/{pageno}/{index}"),
+                        "title": format!("result {pageno}-{index}"),
+                        "content": "content",
+                    })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        (StatusCode::OK, json!({ "results": results }))
+    }
+
+    fn urls(response: &SearchToolResponse) -> Vec<String> {
+        response
+            .results
+            .iter()
+            .map(|result| {
+                result
+                    .url
+                    .trim_start_matches("https://example.com/")
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn rerank_picks_from_candidates_beyond_limit() {
+        let base_url = spawn_searxng(|params: &HashMap<String, String>| {
+            let (status, mut body) = paged_results(params);
+            if params["pageno"] == "1" {
+                body["results"][12]["title"] = json!("tokio runtime");
+            }
+            (status, body)
+        })
+        .await;
+        let client = SearxngClient::new_with_rerank(base_url, Arc::new(Bm25Reranker::new()));
+        let options = SearchOptions {
+            limit: 5,
+            ..SearchOptions::default()
+        };
+
+        let response = client.search("tokio", None, &options).await.unwrap();
+        assert_eq!(response.rerank, RerankStatus::Applied);
+        assert_eq!(response.results.len(), 5);
+        assert_eq!(urls(&response)[0], "1/12");
+        // 候选池（limit 的 3 倍）已全部参与排序，下一页从候选池之后继续
+        assert_eq!((response.next_page, response.next_offset), (Some(1), 15));
+    }
+
+    #[tokio::test]
+    async fn cursor_resumes_inside_partially_used_page() {
+        let client = SearxngClient::new(spawn_searxng(paged_results).await);
+        let search = |page, offset, limit| {
+            let client = client.clone();
+            async move {
+                let options = SearchOptions {
+                    limit,
+                    page,
+                    offset,
+                    ..SearchOptions::default()
+                };
+                client.search("rust", None, &options).await.unwrap()
+            }
+        };
+
+        let first = search(1, 0, 5).await;
+        assert_eq!(urls(&first), ["1/0", "1/1", "1/2", "1/3", "1/4"]);
+        assert_eq!((first.next_page, first.next_offset), (Some(1), 5));
+
+        let second = search(1, 5, 5).await;
+        assert_eq!(urls(&second), ["1/5", "1/6", "1/7", "1/8", "1/9"]);
+        assert_eq!((second.next_page, second.next_offset), (Some(1), 10));
+
+        // 跨页时从下一页继续，游标指向下一页中第一条未使用的结果
+        let across = search(1, 15, 10).await;
+        assert_eq!(urls(&across)[..5], ["1/15", "1/16", "1/17", "1/18", "1/19"]);
+        assert_eq!(urls(&across)[5..], ["2/0", "2/1", "2/2", "2/3", "2/4"]);
+        assert_eq!((across.next_page, across.next_offset), (Some(2), 5));
+
+        let last = search(2, 10, 20).await;
+        assert_eq!(last.results.len(), 10);
+        assert_eq!(last.next_page, None);
+    }
+
+    #[tokio::test]
+    async fn whole_page_used_moves_to_next_page() {
+        let client = SearxngClient::new(spawn_searxng(paged_results).await);
+        let options = SearchOptions {
+            limit: 20,
+            ..SearchOptions::default()
+        };
+        let response = client.search("rust", None, &options).await.unwrap();
+        assert_eq!(response.results.len(), 20);
+        assert_eq!((response.next_page, response.next_offset), (Some(2), 0));
+    }
+
+    fn failing(_: &HashMap<String, String>) -> (StatusCode, Value) {
+        (StatusCode::BAD_GATEWAY, json!({}))
+    }
+
+    fn pooled_client(base_urls: Vec<String>) -> SearxngClient {
+        let pool = Arc::new(UpstreamPool::new(base_urls, BalanceStrategy::RoundRobin));
+        let http = HttpConfig {
+            max_retries: 0,
+            ..HttpConfig::default()
+        };
+        SearxngClient::with_pool(pool, None, http).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_next_upstream() {
+        let broken = spawn_searxng(failing).await;
+        let healthy = spawn_searxng(paged_results).await;
+        let client = pooled_client(vec![broken, healthy]);
+
+        for _ in 0..4 {
+            let response = client
+                .search("rust", None, &SearchOptions::default())
+                .await
+                .unwrap();
+            assert!(response.success);
+            assert_eq!(response.results.len(), 20);
+        }
+        let status = client.pool().status();
+        assert!(status[0].failures > 0);
+        assert_eq!(status[1].failures, 0);
+        assert_eq!(status[1].requests, 4);
+    }
+
+    #[tokio::test]
+    async fn reports_error_when_all_upstreams_fail() {
+        let client = pooled_client(vec![
+            spawn_searxng(failing).await,
+            spawn_searxng(failing).await,
+        ]);
+        let err = client
+            .search("rust", None, &SearchOptions::default())
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("all 2 searxng upstreams failed"));
+    }
+
+    /// 未排除 google 时 google 被限流，只返回 1 条结果；排除后返回 6 条
+    fn rate_limited(params: &HashMap<String, String>) -> (StatusCode, Value) {
+        if params.get("pageno").map(String::as_str) != Some("1") {
+            return (StatusCode::OK, json!({ "results": [] }));
+        }
+        let disabled = params.get("disabled_engines").cloned().unwrap_or_default();
+        let engines = params.get("engines").cloned().unwrap_or_default();
+        let excluded = disabled == "google__general" || engines == "bing";
+        let count = if excluded { 6 } else { 1 };
+        let results: Vec<Value> = (0..count)
+            .map(|index| json!({ "url": format!("https://example.com/{index}"), "content": "c" }))
+            .collect();
+        let unresponsive = if excluded {
+            json!([])
+        } else {
+            json!([["google", "CAPTCHA"]])
+        };
+        (
+            StatusCode::OK,
+            json!({ "results": results, "unresponsive_engines": unresponsive }),
+        )
+    }
+
+    #[tokio::test]
+    async fn retries_without_unresponsive_engines() {
+        let client = SearxngClient::new(spawn_searxng(rate_limited).await);
+        let response = client
+            .search("rust", None, &SearchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(response.results.len(), 6);
+        assert_eq!(response.excluded_engines, ["google"]);
+        assert_eq!(response.warnings.len(), 1);
+        assert_eq!(response.warnings[0].reason, "CAPTCHA");
+    }
+
+    #[tokio::test]
+    async fn retry_removes_unresponsive_engine_from_explicit_list() {
+        let client = SearxngClient::new(spawn_searxng(rate_limited).await);
+        let options = SearchOptions {
+            filters: SearchFilters::new(None, None, None, &["google".into(), "bing".into()])
+                .unwrap(),
+            ..SearchOptions::default()
+        };
+        let response = client.search("rust", None, &options).await.unwrap();
+        assert_eq!(response.results.len(), 6);
+        assert_eq!(response.excluded_engines, ["google"]);
+    }
+
+    #[tokio::test]
+    async fn no_retry_when_only_explicit_engine_is_unresponsive() {
+        let client = SearxngClient::new(spawn_searxng(rate_limited).await);
+        let options = SearchOptions {
+            filters: SearchFilters::new(None, None, None, &["google".into()]).unwrap(),
+            ..SearchOptions::default()
+        };
+        let response = client.search("rust", None, &options).await.unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert!(response.excluded_engines.is_empty());
+    }
+
+    #[tokio::test]
+    async fn min_score_is_ignored_for_bm25_scores() {
+        let base_url = spawn_searxng(paged_results).await;
+        let client = SearxngClient::new_with_rerank(base_url, Arc::new(Bm25Reranker::new()));
+        let options = SearchOptions {
+            min_score: Some(0.9),
+            ..SearchOptions::default()
+        };
+
+        let response = client.search("result 1-3", None, &options).await.unwrap();
+        assert_eq!(response.rerank, RerankStatus::Applied);
+        assert_eq!(response.results.len(), 20);
+        assert_eq!(urls(&response)[0], "1/3");
+        assert_eq!(response.results[0].relevance_score, Some(1.0));
+        assert!(response.results[1].relevance_score.unwrap() < 0.9);
+    }
 }
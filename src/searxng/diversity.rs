@@ -0,0 +1,202 @@
+use std::collections::{HashMap, HashSet};
+
+use reqwest::Url;
+
+use super::types::SearchResult;
+use crate::rerank::bm25::tokenize;
+
+/// 按最大边际相关性（MMR）重新排列结果
+///
+/// 每一步选择 `lambda * 相关性 - (1 - lambda) * 与已选结果的最大相似度` 最高的结果。
+/// 相关性取 `relevance_score`（按本次结果归一化到 0~1），未经重排序时按当前名次递减；
+/// 相似度为标题与摘要词集合的 Jaccard 系数。`lambda` 为 1 时保持原顺序。
+pub fn mmr_rerank(results: Vec<SearchResult>, lambda: f64) -> Vec<SearchResult> {
+    if results.len() < 2 || lambda >= 1.0 {
+        return results;
+    }
+
+    let relevance = relevance(&results);
+    let tokens: Vec<HashSet<String>> = results
+        .iter()
+        .map(|result| {
+            let text = match &result.title {
+                Some(title) => format!("{title} {}", result.description),
+                None => result.description.clone(),
+            };
+            tokenize(&text).into_iter().collect()
+        })
+        .collect();
+
+    let mut remaining: Vec<usize> = (0..results.len()).collect();
+    // 每个候选与已选结果的最大相似度
+    let mut max_similarity = vec![0.0_f64; results.len()];
+    let mut order = Vec::with_capacity(results.len());
+    while !remaining.is_empty() {
+        // 得分相同时取名次靠前的候选
+        let (position, &chosen) = remaining
+            .iter()
+            .enumerate()
+            .max_by(|&(_, &a), &(_, &b)| {
+                let score_a = lambda * relevance[a] - (1.0 - lambda) * max_similarity[a];
+                let score_b = lambda * relevance[b] - (1.0 - lambda) * max_similarity[b];
+                score_a.total_cmp(&score_b).then(b.cmp(&a))
+            })
+            .expect("remaining is not empty");
+        remaining.swap_remove(position);
+        order.push(chosen);
+        for &candidate in &remaining {
+            let similarity = jaccard(&tokens[chosen], &tokens[candidate]);
+            max_similarity[candidate] = max_similarity[candidate].max(similarity);
+        }
+    }
+
+    let mut slots: Vec<Option<SearchResult>> = results.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|index| slots[index].take())
+        .collect()
+}
+
+/// 按顺序保留结果，同一域名最多保留 `max_per_domain` 条，超出的结果被丢弃
+pub fn cap_per_domain(results: Vec<SearchResult>, max_per_domain: usize) -> Vec<SearchResult> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    results
+        .into_iter()
+        .filter(|result| {
+            let count = counts.entry(domain(&result.url)).or_default();
+            *count += 1;
+            *count <= max_per_domain
+        })
+        .collect()
+}
+
+/// 结果所属的域名（主机名去掉 `www.` 前缀），无法解析时为原地址
+fn domain(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .map(|host| host.strip_prefix("www.").unwrap_or(&host).to_string())
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn relevance(results: &[SearchResult]) -> Vec<f64> {
+    let scores: Option<Vec<f64>> = results
+        .iter()
+        .map(|result| result.relevance_score)
+        .collect();
+    let Some(scores) = scores else {
+        let count = results.len() as f64;
+        return (0..results.len())
+            .map(|rank| 1.0 - rank as f64 / count)
+            .collect();
+    };
+
+    let min = scores.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max - min <= f64::EPSILON {
+        return vec![1.0; scores.len()];
+    }
+    scores
+        .into_iter()
+        .map(|score| (score - min) / (max - min))
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    intersection as f64 / (a.len() + b.len() - intersection) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(url: &str, title: &str, relevance_score: Option<f64>) -> SearchResult {
+        SearchResult {
+            title: Some(title.to_string()),
+            relevance_score,
+            ..SearchResult::with_url(url)
+        }
+    }
+
+    fn urls(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|result| result.url.as_str()).collect()
+    }
+
+    #[test]
+    fn mmr_demotes_near_duplicates() {
+        let results = vec![
+            result("https://a.com", "rust async tutorial", Some(0.9)),
+            result("https://b.com", "rust async tutorial guide", Some(0.85)),
+            result("https://c.com", "tokio runtime internals", Some(0.8)),
+        ];
+        let reranked = mmr_rerank(results, 0.5);
+        assert_eq!(
+            urls(&reranked),
+            ["https://a.com", "https://c.com", "https://b.com"]
+        );
+    }
+
+    #[test]
+    fn mmr_with_lambda_one_keeps_order() {
+        let results = vec![
+            result("https://a.com", "same words", Some(0.1)),
+            result("https://b.com", "same words", Some(0.9)),
+        ];
+        let reranked = mmr_rerank(results, 1.0);
+        assert_eq!(urls(&reranked), ["https://a.com", "https://b.com"]);
+    }
+
+    #[test]
+    fn mmr_without_scores_uses_rank() {
+        let results = vec![
+            result("https://a.com", "alpha", None),
+            result("https://b.com", "beta", None),
+            result("https://c.com", "gamma", None),
+        ];
+        let reranked = mmr_rerank(results, 0.7);
+        assert_eq!(
+            urls(&reranked),
+            ["https://a.com", "https://b.com", "https://c.com"]
+        );
+    }
+
+    #[test]
+    fn caps_results_per_domain() {
+        let results = vec![
+            result("https://www.a.com/1", "one", None),
+            result("https://a.com/2", "two", None),
+            result("https://b.com/1", "three", None),
+            result("https://a.com/3", "four", None),
+            result("not a url", "five", None),
+        ];
+        let capped = cap_per_domain(results, 2);
+        assert_eq!(
+            urls(&capped),
+            [
+                "https://www.a.com/1",
+                "https://a.com/2",
+                "https://b.com/1",
+                "not a url"
+            ]
+        );
+    }
+
+    #[test]
+    fn relevance_is_min_max_normalized() {
+        let results = vec![
+            result("https://a.com", "a", Some(2.0)),
+            result("https://b.com", "b", Some(4.0)),
+            result("https://c.com", "c", Some(3.0)),
+        ];
+        assert_eq!(relevance(&results), [0.0, 1.0, 0.5]);
+        let tied = vec![
+            result("https://a.com", "a", Some(0.3)),
+            result("https://b.com", "b", Some(0.3)),
+        ];
+        assert_eq!(relevance(&tied), [1.0, 1.0]);
+    }
+}
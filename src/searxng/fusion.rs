@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::types::{QuerySearchResult, SearchResult};
+
+/// RRF 平滑常数，取论文推荐值 60，降低个别列表头部名次的权重
+pub const RRF_K: f64 = 60.0;
+
+/// 只用于来源追踪、不影响页面内容的查询参数
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "msclkid", "spm"];
+
+/// 融合多个 query（及类别）后的一条结果
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FusedSearchResult {
+    /// 排名最靠前的一次出现的结果，engines 为所有出现的并集
+    #[serde(flatten)]
+    pub result: SearchResult,
+    /// 倒数排名融合得分：各结果列表中 `1 / (60 + 名次)` 之和
+    pub fusion_score: f64,
+    /// 返回该结果的 query，按首次命中顺序
+    pub matched_queries: Vec<String>,
+    /// 返回该结果的搜索类别
+    pub matched_categories: Vec<String>,
+}
+
+/// 按倒数排名融合（Reciprocal Rank Fusion）合并多次搜索的结果
+///
+/// 名次取各次搜索最终返回的顺序（重排序之后），失败的搜索不参与融合。
+/// 结果按 [`canonical_url`] 去重，得分相同时保持首次出现的顺序。
+pub fn reciprocal_rank_fusion(
+    searches: &[QuerySearchResult],
+    limit: usize,
+) -> Vec<FusedSearchResult> {
+    // (融合结果, 当前代表结果的名次)
+    let mut fused: Vec<(FusedSearchResult, usize)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for search in searches.iter().filter(|search| search.success) {
+        for (rank, result) in search.results.iter().enumerate() {
+            let contribution = 1.0 / (RRF_K + (rank + 1) as f64);
+            let key = canonical_url(&result.url);
+            let Some(&position) = positions.get(&key) else {
+                positions.insert(key, fused.len());
+                fused.push((
+                    FusedSearchResult {
+                        result: result.clone(),
+                        fusion_score: contribution,
+                        matched_queries: vec![search.query.clone()],
+                        matched_categories: vec![search.category.clone()],
+                    },
+                    rank,
+                ));
+                continue;
+            };
+
+            let (entry, best_rank) = &mut fused[position];
+            entry.fusion_score += contribution;
+            if !entry.matched_queries.contains(&search.query) {
+                entry.matched_queries.push(search.query.clone());
+            }
+            if !entry.matched_categories.contains(&search.category) {
+                entry.matched_categories.push(search.category.clone());
+            }
+            let mut engines = std::mem::take(&mut entry.result.engines);
+            for engine in &result.engines {
+                if !engines.contains(engine) {
+                    engines.push(engine.clone());
+                }
+            }
+            if rank < *best_rank {
+                entry.result = result.clone();
+                *best_rank = rank;
+            }
+            entry.result.engines = engines;
+        }
+    }
+
+    let mut fused: Vec<FusedSearchResult> = fused.into_iter().map(|(entry, _)| entry).collect();
+    // 稳定排序，得分相同时保持首次出现的顺序
+    fused.sort_by(|a, b| b.fusion_score.total_cmp(&a.fusion_score));
+    fused.truncate(limit);
+    fused
+}
+
+/// 用于去重的规范化 URL
+///
+/// 忽略协议、`www.` 前缀、片段、结尾的 `/` 以及 `utm_*` 等追踪参数；无法解析的地址原样返回。
+pub fn canonical_url(url: &str) -> String {
+    let Ok(parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let Some(host) = parsed.host_str() else {
+        return url.to_string();
+    };
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let port = parsed
+        .port()
+        .map(|port| format!(":{port}"))
+        .unwrap_or_default();
+    let path = parsed.path().trim_end_matches('/');
+    let query: Vec<&str> = parsed
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split_once('=').map_or(*pair, |(name, _)| name);
+            !name.is_empty()
+                && !name.to_ascii_lowercase().starts_with("utm_")
+                && !TRACKING_PARAMS.contains(&name)
+        })
+        .collect();
+
+    if query.is_empty() {
+        format!("{host}{port}{path}")
+    } else {
+        format!("{host}{port}{path}?{}", query.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(url: &str, engine: &str) -> SearchResult {
+        SearchResult {
+            engines: vec![engine.to_string()],
+            ..SearchResult::with_url(url)
+        }
+    }
+
+    fn search(query: &str, category: &str, results: Vec<SearchResult>) -> QuerySearchResult {
+        QuerySearchResult::succeeded(query, category, results)
+    }
+
+    fn urls(fused: &[FusedSearchResult]) -> Vec<&str> {
+        fused
+            .iter()
+            .map(|entry| entry.result.url.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sums_reciprocal_ranks_across_searches() {
+        let searches = [
+            search(
+                "rust",
+                "general",
+                vec![
+                    result("https://a.com", "google"),
+                    result("https://b.com", "google"),
+                ],
+            ),
+            search(
+                "rust lang",
+                "it",
+                vec![
+                    result("https://c.com", "bing"),
+                    result("https://b.com/", "bing"),
+                ],
+            ),
+        ];
+        let fused = reciprocal_rank_fusion(&searches, 10);
+
+        assert_eq!(
+            urls(&fused),
+            ["https://b.com", "https://a.com", "https://c.com"]
+        );
+        let expected = 2.0 / (RRF_K + 2.0);
+        assert!((fused[0].fusion_score - expected).abs() < 1e-12);
+        assert_eq!(fused[0].matched_queries, ["rust", "rust lang"]);
+        assert_eq!(fused[0].matched_categories, ["general", "it"]);
+        assert_eq!(fused[0].result.engines, ["google", "bing"]);
+        assert!((fused[1].fusion_score - fused[2].fusion_score).abs() < 1e-12);
+    }
+
+    #[test]
+    fn keeps_best_ranked_occurrence() {
+        let mut late = result("https://www.a.com/page", "google");
+        late.title = Some("late".to_string());
+        let mut early = result("http://a.com/page", "bing");
+        early.title = Some("early".to_string());
+        let searches = [
+            search(
+                "one",
+                "general",
+                vec![result("https://x.com", "google"), late],
+            ),
+            search("two", "general", vec![early]),
+        ];
+        let fused = reciprocal_rank_fusion(&searches, 10);
+
+        assert_eq!(fused[0].result.title.as_deref(), Some("early"));
+        assert_eq!(fused[0].result.engines, ["google", "bing"]);
+    }
+
+    #[test]
+    fn skips_failed_searches_and_truncates() {
+        let mut failed = search("bad", "general", vec![result("https://z.com", "google")]);
+        failed.success = false;
+        let searches = [
+            failed,
+            search(
+                "good",
+                "general",
+                vec![
+                    result("https://a.com", "google"),
+                    result("https://b.com", "google"),
+                    result("https://c.com", "google"),
+                ],
+            ),
+        ];
+        let fused = reciprocal_rank_fusion(&searches, 2);
+
+        assert_eq!(urls(&fused), ["https://a.com", "https://b.com"]);
+        assert_eq!(fused[0].matched_queries, ["good"]);
+    }
+
+    #[test]
+    fn canonicalizes_urls() {
+        assert_eq!(
+            canonical_url("https://www.Example.com/docs/?utm_source=x&id=1&fbclid=y#top"),
+            "example.com/docs?id=1"
+        );
+        assert_eq!(canonical_url("http://example.com/"), "example.com");
+        assert_eq!(
+            canonical_url("https://example.com:8443/a"),
+            "example.com:8443/a"
+        );
+        assert_eq!(canonical_url("https://example.com/a?&"), "example.com/a");
+        assert_eq!(canonical_url("not a url"), "not a url");
+    }
+}
@@ -1,4 +1,7 @@
-use super::types::{SearchResult, SearxngResultItem};
+use super::types::{
+    DirectAnswer, Infobox, InfoboxAttribute, InfoboxLink, SearchResult, SearxngAnswer,
+    SearxngInfobox, SearxngResultItem,
+};
 
 pub fn map_result_item(category: &str, item: SearxngResultItem) -> Option<SearchResult> {
     match category {
@@ -8,18 +11,124 @@ pub fn map_result_item(category: &str, item: SearxngResultItem) -> Option<Search
 }
 
 fn map_text_item(item: SearxngResultItem) -> Option<SearchResult> {
-    let url = normalize(item.url)?;
-    let description = normalize(item.content).or_else(|| normalize(item.title))?;
-    Some(SearchResult { url, description })
+    let url = normalize(item.url.clone())?;
+    let title = normalize(item.title.clone());
+    let description = normalize(item.content.clone()).or_else(|| title.clone())?;
+    let thumbnail = normalize(item.thumbnail.clone()).or_else(|| normalize(item.img_src.clone()));
+    Some(build_result(url, title, description, thumbnail, item))
 }
 
 fn map_image_item(item: SearxngResultItem) -> Option<SearchResult> {
-    let url = normalize(item.img_src)?;
-    let description = normalize(item.title).or_else(|| normalize(item.content))?;
-    Some(SearchResult { url, description })
+    let url = normalize(item.img_src.clone())?;
+    let title = normalize(item.title.clone());
+    let description = title.clone().or_else(|| normalize(item.content.clone()))?;
+    let thumbnail =
+        normalize(item.thumbnail_src.clone()).or_else(|| normalize(item.thumbnail.clone()));
+    let source_url = normalize(item.url.clone());
+    let resolution = normalize(item.resolution.clone());
+    let img_format = normalize(item.img_format.clone());
+    Some(SearchResult {
+        source_url,
+        resolution,
+        img_format,
+        ..build_result(url, title, description, thumbnail, item)
+    })
+}
+
+/// 填充与类别无关的元数据字段
+fn build_result(
+    url: String,
+    title: Option<String>,
+    description: String,
+    thumbnail: Option<String>,
+    item: SearxngResultItem,
+) -> SearchResult {
+    // 旧版本 searXNG 只返回单个 engine 字段
+    let engines = match item.engines {
+        Some(engines) if !engines.is_empty() => engines,
+        _ => normalize(item.engine).into_iter().collect(),
+    };
+    SearchResult {
+        url,
+        title,
+        description,
+        engines,
+        score: item.score,
+        published_date: normalize(item.published_date),
+        category: normalize(item.category),
+        thumbnail,
+        positions: item.positions.unwrap_or_default(),
+        relevance_score: None,
+        source_url: None,
+        resolution: None,
+        img_format: None,
+    }
+}
+
+pub fn map_answer(answer: SearxngAnswer) -> Option<DirectAnswer> {
+    match answer {
+        SearxngAnswer::Text(answer) => Some(DirectAnswer {
+            answer: normalize(Some(answer))?,
+            url: None,
+            engine: None,
+        }),
+        SearxngAnswer::Object {
+            answer,
+            url,
+            engine,
+        } => Some(DirectAnswer {
+            answer: normalize(answer)?,
+            url: normalize(url),
+            engine: normalize(engine),
+        }),
+    }
+}
+
+pub fn map_infobox(infobox: SearxngInfobox) -> Option<Infobox> {
+    let attributes = infobox
+        .attributes
+        .into_iter()
+        .filter_map(|attribute| {
+            let value = match attribute.value? {
+                serde_json::Value::String(value) => value,
+                serde_json::Value::Number(value) => value.to_string(),
+                serde_json::Value::Bool(value) => value.to_string(),
+                // 图片等非文本属性对文本检索没有意义
+                _ => return None,
+            };
+            Some(InfoboxAttribute {
+                label: normalize(attribute.label)?,
+                value: normalize(Some(value))?,
+            })
+        })
+        .collect();
+    let urls = infobox
+        .urls
+        .into_iter()
+        .filter_map(|link| {
+            Some(InfoboxLink {
+                title: normalize(link.title)?,
+                url: normalize(link.url)?,
+            })
+        })
+        .collect();
+    let engines = match infobox.engines {
+        Some(engines) if !engines.is_empty() => engines,
+        _ => normalize(infobox.engine).into_iter().collect(),
+    };
+
+    Some(Infobox {
+        title: normalize(infobox.infobox)?,
+        url: normalize(infobox.id).filter(|id| id.starts_with("http")),
+        content: normalize(infobox.content),
+        image_url: normalize(infobox.img_src),
+        attributes,
+        urls,
+        engines,
+    })
 }
 
-fn normalize(value: Option<String>) -> Option<String> {
+pub(crate) fn normalize(value: Option<String>) -> Option<String> {
     value.and_then(|value| {
         let trimmed = value.trim();
         if trimmed.is_empty() {
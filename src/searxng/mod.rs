@@ -1,3 +1,7 @@
+pub mod cache;
 pub mod client;
+pub mod diversity;
+pub mod fusion;
 pub mod mapper;
+pub mod pool;
 pub mod types;
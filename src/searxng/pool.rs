@@ -0,0 +1,349 @@
+use std::{
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use reqwest::Client;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+/// 连续失败达到该次数后将实例标记为不健康
+const UNHEALTHY_AFTER_FAILURES: u32 = 3;
+/// 不健康实例的冷却时间，期间只在没有健康实例时使用
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+/// 延迟与错误率滑动平均的平滑系数
+const EWMA_ALPHA: f64 = 0.2;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 多个 searXNG 实例之间的选择策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceStrategy {
+    /// 按顺序轮询
+    RoundRobin,
+    /// 按平均延迟的倒数加权随机选择，延迟越低被选中概率越高
+    Latency,
+}
+
+impl FromStr for BalanceStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().replace('-', "_").as_str() {
+            "round_robin" => Ok(BalanceStrategy::RoundRobin),
+            "latency" => Ok(BalanceStrategy::Latency),
+            _ => Err("expected `round_robin` or `latency`".to_string()),
+        }
+    }
+}
+
+/// 单个实例的健康状态快照
+#[derive(Debug, Clone, Serialize)]
+pub struct UpstreamStatus {
+    pub url: String,
+    pub healthy: bool,
+    /// 平均延迟（毫秒），尚无成功请求时为空
+    pub latency_ms: Option<f64>,
+    /// 最近请求错误率的滑动平均（0~1）
+    pub error_rate: f64,
+    pub requests: u64,
+    pub failures: u64,
+    pub timeouts: u64,
+}
+
+#[derive(Debug, Default)]
+struct UpstreamStats {
+    ewma_latency_ms: Option<f64>,
+    error_rate: f64,
+    consecutive_failures: u32,
+    requests: u64,
+    failures: u64,
+    timeouts: u64,
+    unhealthy_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Upstream {
+    base_url: String,
+    stats: Mutex<UpstreamStats>,
+}
+
+impl Upstream {
+    fn is_healthy(&self, now: Instant) -> bool {
+        let stats = self.stats.lock().unwrap();
+        stats.unhealthy_until.is_none_or(|until| until <= now)
+    }
+
+    fn latency_ms(&self) -> Option<f64> {
+        self.stats.lock().unwrap().ewma_latency_ms
+    }
+}
+
+/// searXNG 实例池：负责实例选择、被动健康统计与主动探测
+#[derive(Debug)]
+pub struct UpstreamPool {
+    upstreams: Vec<Upstream>,
+    strategy: BalanceStrategy,
+    next: AtomicUsize,
+}
+
+impl UpstreamPool {
+    pub fn new(base_urls: Vec<String>, strategy: BalanceStrategy) -> Self {
+        let upstreams = base_urls
+            .into_iter()
+            .map(|base_url| Upstream {
+                base_url: base_url.trim_end_matches('/').to_string(),
+                stats: Mutex::new(UpstreamStats::default()),
+            })
+            .collect();
+        Self {
+            upstreams,
+            strategy,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.upstreams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upstreams.is_empty()
+    }
+
+    /// 返回本次请求尝试实例的顺序（实例下标）
+    ///
+    /// 健康实例按选择策略排在前面，不健康实例排在最后作为兜底。
+    pub(crate) fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..self.upstreams.len()).partition(|&index| self.upstreams[index].is_healthy(now));
+
+        match self.strategy {
+            BalanceStrategy::RoundRobin => {
+                if !healthy.is_empty() {
+                    let start = self.next.fetch_add(1, Ordering::Relaxed) % healthy.len();
+                    healthy.rotate_left(start);
+                }
+            }
+            BalanceStrategy::Latency => {
+                // 尚未测得延迟的实例优先尝试，以便尽快获得统计数据
+                let weights: Vec<f64> = healthy
+                    .iter()
+                    .map(|&index| match self.upstreams[index].latency_ms() {
+                        Some(latency) => 1.0 / latency.max(1.0),
+                        None => f64::INFINITY,
+                    })
+                    .collect();
+                if let Some(first) = weighted_pick(&weights) {
+                    let picked = healthy.remove(first);
+                    healthy.sort_by(|&a, &b| {
+                        let a = self.upstreams[a].latency_ms().unwrap_or(0.0);
+                        let b = self.upstreams[b].latency_ms().unwrap_or(0.0);
+                        a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                    });
+                    healthy.insert(0, picked);
+                }
+            }
+        }
+
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    pub(crate) fn base_url(&self, index: usize) -> &str {
+        &self.upstreams[index].base_url
+    }
+
+    pub(crate) fn record_success(&self, index: usize, latency: Duration) {
+        let upstream = &self.upstreams[index];
+        let mut stats = upstream.stats.lock().unwrap();
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        stats.ewma_latency_ms = Some(match stats.ewma_latency_ms {
+            Some(previous) => previous + EWMA_ALPHA * (latency_ms - previous),
+            None => latency_ms,
+        });
+        stats.error_rate *= 1.0 - EWMA_ALPHA;
+        stats.requests += 1;
+        stats.consecutive_failures = 0;
+        if stats.unhealthy_until.take().is_some() {
+            info!(upstream = %upstream.base_url, "searxng upstream recovered");
+        }
+    }
+
+    pub(crate) fn record_failure(&self, index: usize, timeout: bool) {
+        let upstream = &self.upstreams[index];
+        let mut stats = upstream.stats.lock().unwrap();
+        stats.error_rate += EWMA_ALPHA * (1.0 - stats.error_rate);
+        stats.requests += 1;
+        stats.failures += 1;
+        if timeout {
+            stats.timeouts += 1;
+        }
+        stats.consecutive_failures += 1;
+        if stats.consecutive_failures >= UNHEALTHY_AFTER_FAILURES {
+            if stats.unhealthy_until.is_none() {
+                warn!(
+                    upstream = %upstream.base_url,
+                    failures = stats.consecutive_failures,
+                    "searxng upstream marked unhealthy"
+                );
+            }
+            stats.unhealthy_until = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+        }
+    }
+
+    pub fn status(&self) -> Vec<UpstreamStatus> {
+        let now = Instant::now();
+        self.upstreams
+            .iter()
+            .map(|upstream| {
+                let healthy = upstream.is_healthy(now);
+                let stats = upstream.stats.lock().unwrap();
+                UpstreamStatus {
+                    url: upstream.base_url.clone(),
+                    healthy,
+                    latency_ms: stats.ewma_latency_ms,
+                    error_rate: stats.error_rate,
+                    requests: stats.requests,
+                    failures: stats.failures,
+                    timeouts: stats.timeouts,
+                }
+            })
+            .collect()
+    }
+
+    /// 启动后台任务，定期请求每个实例的 `/healthz` 更新健康状态
+    pub fn spawn_health_checks(self: &Arc<Self>, http: Client, interval: Duration) {
+        let pool = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for index in 0..pool.upstreams.len() {
+                    pool.probe(&http, index).await;
+                }
+            }
+        });
+    }
+
+    async fn probe(&self, http: &Client, index: usize) {
+        let endpoint = format!("{}/healthz", self.base_url(index));
+        let started = Instant::now();
+        let result = http
+            .get(&endpoint)
+            .timeout(PROBE_TIMEOUT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match result {
+            Ok(_) => {
+                debug!(
+                    upstream = %self.base_url(index),
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "searxng health probe ok"
+                );
+                let mut stats = self.upstreams[index].stats.lock().unwrap();
+                stats.consecutive_failures = 0;
+                if stats.unhealthy_until.take().is_some() {
+                    info!(upstream = %self.base_url(index), "searxng upstream recovered");
+                }
+            }
+            Err(err) => {
+                warn!(upstream = %self.base_url(index), error = %err, "searxng health probe failed");
+                // 探测失败直接标记为不健康，不必等待连续失败
+                let mut stats = self.upstreams[index].stats.lock().unwrap();
+                stats.consecutive_failures =
+                    stats.consecutive_failures.max(UNHEALTHY_AFTER_FAILURES);
+                stats.unhealthy_until = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+            }
+        }
+    }
+}
+
+/// 按权重随机选取下标，权重为无穷大的项优先
+fn weighted_pick(weights: &[f64]) -> Option<usize> {
+    if weights.is_empty() {
+        return None;
+    }
+    if let Some(index) = weights.iter().position(|weight| weight.is_infinite()) {
+        return Some(index);
+    }
+    let total: f64 = weights.iter().sum();
+    let mut target = fastrand::f64() * total;
+    for (index, weight) in weights.iter().enumerate() {
+        if target < *weight {
+            return Some(index);
+        }
+        target -= weight;
+    }
+    Some(weights.len() - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(strategy: BalanceStrategy) -> UpstreamPool {
+        UpstreamPool::new(
+            vec![
+                "http://a".to_string(),
+                "http://b/".to_string(),
+                "http://c".to_string(),
+            ],
+            strategy,
+        )
+    }
+
+    #[test]
+    fn round_robin_rotates_start() {
+        let pool = pool(BalanceStrategy::RoundRobin);
+        assert_eq!(pool.candidates(), [0, 1, 2]);
+        assert_eq!(pool.candidates(), [1, 2, 0]);
+        assert_eq!(pool.candidates(), [2, 0, 1]);
+        assert_eq!(pool.base_url(1), "http://b");
+    }
+
+    #[test]
+    fn unhealthy_upstream_is_tried_last() {
+        let pool = pool(BalanceStrategy::RoundRobin);
+        for _ in 0..UNHEALTHY_AFTER_FAILURES {
+            pool.record_failure(0, false);
+        }
+        assert!(!pool.status()[0].healthy);
+        for _ in 0..3 {
+            assert_eq!(pool.candidates().last(), Some(&0));
+        }
+
+        pool.record_success(0, Duration::from_millis(10));
+        assert!(pool.status()[0].healthy);
+        assert_eq!(pool.status()[0].failures, UNHEALTHY_AFTER_FAILURES as u64);
+    }
+
+    #[test]
+    fn latency_strategy_prefers_unmeasured_then_fastest() {
+        let pool = pool(BalanceStrategy::Latency);
+        pool.record_success(0, Duration::from_millis(300));
+        pool.record_success(1, Duration::from_millis(20));
+        assert_eq!(pool.candidates()[0], 2);
+
+        pool.record_success(2, Duration::from_millis(100));
+        let candidates = pool.candidates();
+        // 首个实例按延迟加权随机选取，其余按延迟升序
+        let expected: Vec<usize> = [1, 2, 0]
+            .into_iter()
+            .filter(|index| *index != candidates[0])
+            .collect();
+        assert_eq!(candidates[1..], expected[..]);
+    }
+
+    #[test]
+    fn weighted_pick_prefers_infinite_weight() {
+        assert_eq!(weighted_pick(&[]), None);
+        assert_eq!(weighted_pick(&[1.0, f64::INFINITY, 2.0]), Some(1));
+        assert_eq!(weighted_pick(&[0.0, 1.0]), Some(1));
+    }
+}
@@ -1,10 +1,322 @@
+use anyhow::Result;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::fusion::FusedSearchResult;
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchResult {
+    /// 结果地址；图片类为图片原图地址
     pub url: String,
+    pub title: Option<String>,
+    /// 结果摘要（snippet），没有摘要时为标题
     pub description: String,
+    /// 返回该结果的 searXNG 引擎
+    #[serde(default)]
+    pub engines: Vec<String>,
+    /// searXNG 综合各引擎排名计算的得分，越高越靠前
+    pub score: Option<f64>,
+    /// 发布时间（ISO 8601），来源未提供时为空
+    pub published_date: Option<String>,
+    /// searXNG 归类的结果类别，如 `general`、`news`
+    pub category: Option<String>,
+    /// 缩略图地址
+    pub thumbnail: Option<String>,
+    /// 该结果在各引擎结果中的排名位置（从 1 开始）
+    #[serde(default)]
+    pub positions: Vec<u32>,
+    /// 重排序给出的相关性得分，越高越相关；未经重排序时为空
+    #[serde(default)]
+    pub relevance_score: Option<f64>,
+    /// 图片所在的来源页面，仅图片类结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// 图片分辨率，如 `1920 x 1080`，仅图片类结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    /// 图片格式，如 `jpeg`，仅图片类结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub img_format: Option<String>,
+}
+
+#[cfg(test)]
+impl SearchResult {
+    /// 测试用：标题与地址相同，其余字段为空
+    pub(crate) fn with_url(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            title: Some(url.to_string()),
+            description: String::new(),
+            engines: Vec::new(),
+            score: None,
+            published_date: None,
+            category: None,
+            thumbnail: None,
+            positions: Vec::new(),
+            relevance_score: None,
+            source_url: None,
+            resolution: None,
+            img_format: None,
+        }
+    }
+}
+
+/// 单次搜索的数量与分页控制
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// 本次使用的候选条数，也是最多返回的结果条数
+    pub limit: usize,
+    /// 起始 searXNG 页码（从 1 开始）
+    pub page: usize,
+    /// 跳过起始页的前若干条结果，与 `page` 一起组成上一次返回的翻页游标
+    pub offset: usize,
+    /// 透传给 searXNG 的过滤参数
+    pub filters: SearchFilters,
+    /// 重排序后只保留得分最高的前 N 条（不超过 limit），并转发给支持的重排序提供方
+    pub top_n: Option<usize>,
+    /// 丢弃重排序得分低于该值的结果，仅在结果经过得分尺度固定的重排序模型排序时生效（BM25 不生效）
+    pub min_score: Option<f64>,
+    /// 覆盖本次调用的重排序 query 模板或指令，见 [`QueryTemplates::render`](crate::rerank::QueryTemplates::render)
+    pub rerank_instruction: Option<String>,
+    /// 按最大边际相关性重新排列结果的权衡系数（0~1），越小越偏向多样性；为空时不调整
+    pub mmr_lambda: Option<f64>,
+    /// 同一域名最多保留的结果数；为空时不限制
+    pub max_per_domain: Option<usize>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 20,
+            page: 1,
+            offset: 0,
+            filters: SearchFilters::default(),
+            top_n: None,
+            min_score: None,
+            rerank_instruction: None,
+            mmr_lambda: None,
+            max_per_domain: None,
+        }
+    }
+}
+
+/// 结果发布时间范围，对应 searXNG 的 `time_range` 参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeRange {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeRange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeRange::Day => "day",
+            TimeRange::Week => "week",
+            TimeRange::Month => "month",
+            TimeRange::Year => "year",
+        }
+    }
+}
+
+/// 安全搜索级别，对应 searXNG 的 `safesearch` 参数（0/1/2）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SafeSearch {
+    Off,
+    Moderate,
+    Strict,
+}
+
+impl SafeSearch {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            SafeSearch::Off => "0",
+            SafeSearch::Moderate => "1",
+            SafeSearch::Strict => "2",
+        }
+    }
+}
+
+/// searXNG 搜索过滤参数，未设置的字段使用实例默认配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SearchFilters {
+    pub time_range: Option<TimeRange>,
+    /// 语言代码，如 `de`、`zh-CN`，或 `all`
+    pub language: Option<String>,
+    pub safesearch: Option<SafeSearch>,
+    /// 只使用指定的引擎，如 `google`、`wikipedia`
+    pub engines: Vec<String>,
+    /// 需要排除的引擎，由客户端在无响应引擎重试时设置
+    pub excluded_engines: Vec<String>,
+}
+
+impl SearchFilters {
+    /// 最多可指定的引擎数
+    pub const MAX_ENGINES: usize = 20;
+
+    /// 校验并规范化语言代码与引擎列表
+    pub fn new(
+        time_range: Option<TimeRange>,
+        language: Option<&str>,
+        safesearch: Option<SafeSearch>,
+        engines: &[String],
+    ) -> Result<Self> {
+        let language = language
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(normalize_language)
+            .transpose()?;
+
+        let mut normalized_engines: Vec<String> = Vec::new();
+        for engine in engines {
+            let engine = engine.trim().to_ascii_lowercase();
+            if engine.is_empty() {
+                continue;
+            }
+            if !engine
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, ' ' | '_' | '-' | '.'))
+            {
+                anyhow::bail!("invalid engine name: {engine}");
+            }
+            if !normalized_engines.contains(&engine) {
+                normalized_engines.push(engine);
+            }
+        }
+        if normalized_engines.len() > Self::MAX_ENGINES {
+            anyhow::bail!(
+                "too many engines: {} (max {})",
+                normalized_engines.len(),
+                Self::MAX_ENGINES
+            );
+        }
+
+        Ok(Self {
+            time_range,
+            language,
+            safesearch,
+            engines: normalized_engines,
+            excluded_engines: Vec::new(),
+        })
+    }
+}
+
+/// 接受 `all`、`auto` 或 BCP-47 形式的语言代码（语言[-文字][-地区]），并规范化大小写
+///
+/// 语言小写，4 个字母的文字子标签首字母大写（如 `Hant`），2 个字母的地区大写、3 位数字的地区保持不变。
+fn normalize_language(value: &str) -> Result<String> {
+    let lower = value.to_ascii_lowercase();
+    if lower == "all" || lower == "auto" {
+        return Ok(lower);
+    }
+    let invalid = || {
+        anyhow::anyhow!(
+            "invalid language code: {value} (expected e.g. `en`, `de`, `zh-CN`, `zh-Hant` or `all`)"
+        )
+    };
+
+    let mut subtags = lower.split(['-', '_']);
+    let lang = subtags.next().unwrap_or_default();
+    if !(2..=3).contains(&lang.len()) || !lang.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return Err(invalid());
+    }
+    let mut normalized = lang.to_string();
+    let (mut script_allowed, mut region_allowed) = (true, true);
+    for subtag in subtags {
+        let alphabetic = subtag.chars().all(|ch| ch.is_ascii_alphabetic());
+        let numeric = subtag.chars().all(|ch| ch.is_ascii_digit());
+        normalized.push('-');
+        if script_allowed && subtag.len() == 4 && alphabetic {
+            normalized.push_str(&subtag[..1].to_ascii_uppercase());
+            normalized.push_str(&subtag[1..]);
+            script_allowed = false;
+        } else if region_allowed
+            && ((subtag.len() == 2 && alphabetic) || (subtag.len() == 3 && numeric))
+        {
+            normalized.push_str(&subtag.to_ascii_uppercase());
+            script_allowed = false;
+            region_allowed = false;
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(normalized)
+}
+
+/// 重排序执行情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RerankStatus {
+    /// 未配置重排序，结果保持 searXNG 原始顺序
+    Disabled,
+    /// 没有可排序的结果，未调用重排序
+    Skipped,
+    /// 结果已按重排序得分排列
+    Applied,
+    /// 重排序调用失败，回退为 searXNG 原始顺序
+    Fallback,
+    /// 重排序调用失败，改用本地 BM25 词法排序
+    Lexical,
+}
+
+/// 搜索结果缓存命中情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheStatus {
+    /// 未启用缓存
+    Disabled,
+    /// 所有页面均从 searXNG 获取
+    Miss,
+    /// 所有页面均来自缓存
+    Hit,
+    /// 部分页面来自缓存
+    Partial,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DirectAnswer {
+    pub answer: String,
+    /// 答案来源地址
+    pub url: Option<String>,
+    pub engine: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InfoboxAttribute {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InfoboxLink {
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Infobox {
+    pub title: String,
+    /// 条目地址，通常为 Wikipedia 页面
+    pub url: Option<String>,
+    pub content: Option<String>,
+    pub image_url: Option<String>,
+    /// 结构化事实，如出生日期、人口
+    pub attributes: Vec<InfoboxAttribute>,
+    /// 相关链接，如官网、Wikidata
+    pub urls: Vec<InfoboxLink>,
+    #[serde(default)]
+    pub engines: Vec<String>,
+}
+
+/// 本次搜索中未能正常返回结果的引擎
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EngineWarning {
+    pub engine: String,
+    /// searXNG 给出的原因，如 `CAPTCHA`、`timeout`、`too many requests`
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -13,35 +325,271 @@ pub struct SearchToolResponse {
     pub category: String,
     pub success: bool,
     pub results: Vec<SearchResult>,
+    /// 本次搜索起始的 searXNG 页码
+    pub page: usize,
+    /// 继续获取更多结果时可使用的页码；结果已耗尽时为空
+    pub next_page: Option<usize>,
+    /// 与 `next_page` 一起使用的页内偏移，上一页的结果未全部用完时不为 0
+    pub next_offset: usize,
+    pub rerank: RerankStatus,
+    pub cache: CacheStatus,
+    /// searXNG 直接给出的答案，如单位换算、计算结果
+    pub answers: Vec<DirectAnswer>,
+    /// 知识卡片，如 Wikipedia/Wikidata 条目摘要
+    pub infoboxes: Vec<Infobox>,
+    /// 相关搜索建议，可作为新的 query 重试
+    pub suggestions: Vec<String>,
+    /// 拼写纠正后的 query
+    pub corrections: Vec<String>,
+    /// 引擎估计的结果总数，未知时为空
+    pub number_of_results: Option<u64>,
+    /// 无响应的引擎；结果为空或过少时可据此判断是否被上游限流
+    pub warnings: Vec<EngineWarning>,
+    /// 结果过少时排除无响应引擎重试所排除的引擎，未重试时为空
+    pub excluded_engines: Vec<String>,
     pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct QuerySearchResult {
     pub query: String,
+    /// 搜索类别，如 `general`、`news`
+    pub category: String,
     pub success: bool,
     pub results: Vec<SearchResult>,
+    /// 本次搜索起始的 searXNG 页码
+    pub page: usize,
+    /// 继续获取更多结果时可使用的页码；结果已耗尽时为空
+    pub next_page: Option<usize>,
+    /// 与 `next_page` 一起使用的页内偏移，上一页的结果未全部用完时不为 0
+    pub next_offset: usize,
+    pub rerank: RerankStatus,
+    pub cache: CacheStatus,
+    /// searXNG 直接给出的答案，如单位换算、计算结果
+    pub answers: Vec<DirectAnswer>,
+    /// 知识卡片，如 Wikipedia/Wikidata 条目摘要
+    pub infoboxes: Vec<Infobox>,
+    /// 相关搜索建议，可作为新的 query 重试
+    pub suggestions: Vec<String>,
+    /// 拼写纠正后的 query
+    pub corrections: Vec<String>,
+    /// 引擎估计的结果总数，未知时为空
+    pub number_of_results: Option<u64>,
+    /// 无响应的引擎；结果为空或过少时可据此判断是否被上游限流
+    pub warnings: Vec<EngineWarning>,
+    /// 结果过少时排除无响应引擎重试所排除的引擎，未重试时为空
+    pub excluded_engines: Vec<String>,
     pub error: Option<String>,
 }
 
+impl QuerySearchResult {
+    /// 构造单个 query 失败时的结果
+    pub fn failed(query: String, category: String, page: usize, error: String) -> Self {
+        Self {
+            query,
+            category,
+            success: false,
+            results: Vec::new(),
+            page,
+            next_page: None,
+            next_offset: 0,
+            rerank: RerankStatus::Skipped,
+            cache: CacheStatus::Disabled,
+            answers: Vec::new(),
+            infoboxes: Vec::new(),
+            suggestions: Vec::new(),
+            corrections: Vec::new(),
+            number_of_results: None,
+            warnings: Vec::new(),
+            excluded_engines: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+impl QuerySearchResult {
+    /// 测试用：只有结果列表的成功搜索
+    pub(crate) fn succeeded(query: &str, category: &str, results: Vec<SearchResult>) -> Self {
+        Self {
+            success: true,
+            results,
+            rerank: RerankStatus::Disabled,
+            error: None,
+            ..Self::failed(query.to_string(), category.to_string(), 1, String::new())
+        }
+    }
+}
+
+impl From<SearchToolResponse> for QuerySearchResult {
+    fn from(response: SearchToolResponse) -> Self {
+        Self {
+            query: response.query,
+            category: response.category,
+            success: response.success,
+            results: response.results,
+            page: response.page,
+            next_page: response.next_page,
+            next_offset: response.next_offset,
+            rerank: response.rerank,
+            cache: response.cache,
+            answers: response.answers,
+            infoboxes: response.infoboxes,
+            suggestions: response.suggestions,
+            corrections: response.corrections,
+            number_of_results: response.number_of_results,
+            warnings: response.warnings,
+            excluded_engines: response.excluded_engines,
+            error: response.error,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OpenSearchResponse {
+    /// 所有 query 均成功时为 true
     pub success: bool,
+    /// 部分 query 成功、部分失败时为 true
+    pub partial_success: bool,
     pub search_type: String,
     pub results: Vec<QuerySearchResult>,
+    /// 融合模式下按倒数排名融合合并后的结果；此时各 query 的 results 为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fused: Option<Vec<FusedSearchResult>>,
     pub error: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearxngResponse {
     #[serde(default)]
     pub results: Vec<SearxngResultItem>,
+    #[serde(default)]
+    pub answers: Vec<SearxngAnswer>,
+    #[serde(default)]
+    pub infoboxes: Vec<SearxngInfobox>,
+    #[serde(default)]
+    pub suggestions: Vec<String>,
+    #[serde(default)]
+    pub corrections: Vec<String>,
+    /// 部分引擎无法估计时为 0
+    #[serde(default)]
+    pub number_of_results: Option<f64>,
+    /// `[引擎名, 原因]` 列表
+    #[serde(default)]
+    pub unresponsive_engines: Vec<(String, Option<String>)>,
 }
 
-#[derive(Debug, Deserialize)]
+/// 旧版本 searXNG 的 answers 为字符串列表，新版本为对象列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SearxngAnswer {
+    Text(String),
+    Object {
+        answer: Option<String>,
+        url: Option<String>,
+        engine: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearxngInfobox {
+    pub infobox: Option<String>,
+    pub id: Option<String>,
+    pub content: Option<String>,
+    pub img_src: Option<String>,
+    #[serde(default)]
+    pub urls: Vec<SearxngInfoboxLink>,
+    #[serde(default)]
+    pub attributes: Vec<SearxngInfoboxAttribute>,
+    pub engine: Option<String>,
+    pub engines: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearxngInfoboxLink {
+    pub title: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearxngInfoboxAttribute {
+    pub label: Option<String>,
+    /// 通常为字符串，部分引擎返回数字或图片对象
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearxngResultItem {
     pub url: Option<String>,
     pub content: Option<String>,
     pub title: Option<String>,
+    pub engine: Option<String>,
+    pub engines: Option<Vec<String>>,
+    pub score: Option<f64>,
+    pub category: Option<String>,
+    #[serde(rename = "publishedDate")]
+    pub published_date: Option<String>,
+    pub thumbnail: Option<String>,
+    pub positions: Option<Vec<u32>>,
     pub img_src: Option<String>,
+    pub thumbnail_src: Option<String>,
+    /// 图片分辨率，如 `1920 x 1080`
+    pub resolution: Option<String>,
+    pub img_format: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_language_codes() {
+        assert_eq!(normalize_language("EN").unwrap(), "en");
+        assert_eq!(normalize_language("zh_cn").unwrap(), "zh-CN");
+        assert_eq!(normalize_language("zh-Hant").unwrap(), "zh-Hant");
+        assert_eq!(normalize_language("ZH-HANT").unwrap(), "zh-Hant");
+        assert_eq!(normalize_language("sr_latn").unwrap(), "sr-Latn");
+        assert_eq!(normalize_language("zh-hant-tw").unwrap(), "zh-Hant-TW");
+        assert_eq!(normalize_language("es-419").unwrap(), "es-419");
+        assert_eq!(normalize_language("ALL").unwrap(), "all");
+        assert_eq!(normalize_language("auto").unwrap(), "auto");
+    }
+
+    #[test]
+    fn rejects_invalid_language_codes() {
+        for value in [
+            "e",
+            "english",
+            "en-",
+            "en-US-x",
+            "zh-中国",
+            "d3",
+            "en-abc",
+            "en-12",
+            "zh-TW-Hant",
+            "en-US-GB",
+        ] {
+            assert!(normalize_language(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn filters_trim_language_and_dedupe_engines() {
+        let engines = ["Google".to_string(), " google ".to_string(), String::new()];
+        let filters = SearchFilters::new(None, Some("  "), None, &engines).unwrap();
+        assert_eq!(filters.language, None);
+        assert_eq!(filters.engines, ["google"]);
+
+        let filters = SearchFilters::new(None, Some(" de-at "), None, &[]).unwrap();
+        assert_eq!(filters.language.as_deref(), Some("de-AT"));
+    }
+
+    #[test]
+    fn filters_reject_invalid_engines() {
+        assert!(SearchFilters::new(None, None, None, &["bing;rm".to_string()]).is_err());
+        let engines: Vec<String> = (0..=SearchFilters::MAX_ENGINES)
+            .map(|index| format!("engine{index}"))
+            .collect();
+        assert!(SearchFilters::new(None, None, None, &engines).is_err());
+    }
 }